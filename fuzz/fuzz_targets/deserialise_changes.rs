@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use relic_vcs::core::modifications::Change;
+
+fuzz_target!(|data: &str| {
+    // must never panic, and anything accepted must serialise back into something accepted
+    if let Ok(c) = Change::deserialise_changes(data.to_string()) {
+        let s = c.serialise_changes();
+        let reparsed = Change::deserialise_changes(s.clone()).expect("re-parse failed");
+        assert_eq!(reparsed.serialise_changes(), s);
+    }
+});
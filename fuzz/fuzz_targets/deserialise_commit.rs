@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use relic_vcs::core::commit::Commit;
+
+fuzz_target!(|data: &str| {
+    // must never panic, and anything accepted must serialise back into something accepted
+    if let Ok(c) = Commit::deserialise(data.to_string()) {
+        let s = c.serialise();
+        let reparsed = Commit::deserialise(s.clone()).expect("re-parse failed");
+        assert_eq!(reparsed.serialise(), s);
+    }
+});
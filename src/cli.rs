@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use clap::{arg, value_parser, ArgMatches, Command};
+use clap::{arg, value_parser, ArgGroup, ArgMatches, Command};
 
 use crate::commands as command_module;
 use crate::core::error::RelicError;
@@ -63,9 +63,13 @@ pushing and pulling, are implemented."#,
             Command::new("clone").about("Clone a remote Relic repository in the current directory.")
             .arg_required_else_help(true)
             .arg(
-                arg!([URL] "URL of the remote Relic repository")
+                arg!([URL] "URL of the remote Relic repository (a path or file:// URL)")
                 .required(true)
             )
+            .arg(
+                arg!([DIRECTORY] "Directory to clone into (named after the remote by default)")
+                    .value_parser(value_parser!(PathBuf)),
+            )
         ),
         (
             command_module::detach,
@@ -74,18 +78,20 @@ pushing and pulling, are implemented."#,
         (
             command_module::add,
             Command::new("add")
-                .about("Adds a file(s) to staging")
+                .about("Tracks a file(s) and stages their current content")
                 .arg_required_else_help(true)
                 .arg(
                     arg!([FILE]... "File(s) to add (* for all)")
                         .required(true)
                         .value_parser(value_parser!(PathBuf)),
-                ),
+                )
+                .arg(arg!(-p --patch "Choose which hunks to stage interactively"))
+                .arg(arg!(--hunks <HUNKS> "Stage only these hunks, numbered from 1 across all files (eg : 1,3)").conflicts_with("patch")),
         ),
         (
             command_module::remove,
             Command::new("remove")
-                .about("Removes a file(s) to staging")
+                .about("Untracks a file(s) and removes them from staging")
                 .arg_required_else_help(true)
                 .arg(
                     arg!([FILE]... "File(s) to remove (* for all)")
@@ -93,65 +99,290 @@ pushing and pulling, are implemented."#,
                         .value_parser(value_parser!(PathBuf)),
                 ),
         ),
+        (
+            command_module::unstage,
+            Command::new("unstage")
+                .about("Removes a file(s) from staging, without untracking them")
+                .arg_required_else_help(true)
+                .arg(
+                    arg!([FILE]... "File(s) to unstage")
+                        .required(true)
+                        .value_parser(value_parser!(PathBuf)),
+                ),
+        ),
         (
             command_module::commit,
             Command::new("commit")
                 .about("Commit current changes.")
                 .arg_required_else_help(true)
-                .arg(arg!(-m --message <MESSAGE> "Commit message").required(true))
-                .arg(arg!(-d --description <DESCRIPTION> "Commit description")),
+                .arg(arg!(-m --message <MESSAGE> "Commit message").required_unless_present("amend"))
+                .arg(arg!(-d --description <DESCRIPTION> "Commit description"))
+                .arg(arg!(--amend "Fold the staged changes into the latest pending commit"))
+                .arg(arg!(--"allow-empty" "Commit even if there are no changes").conflicts_with("amend"))
+                .arg(
+                    arg!([PATH]... "Commit the tracked content at these paths instead of the staged changes")
+                        .last(true)
+                        .value_parser(value_parser!(PathBuf)),
+                ),
         ),
         (
             command_module::push,
-            Command::new("push").about("Pushes local changes to remote."),
+            Command::new("push").about("Pushes local changes to remote.")
+                .arg(arg!([REMOTE] "Path or file:// URL of the remote, remembered if none is set"))
+                .arg(arg!(--"set-remote" "Replace the remembered remote with REMOTE").requires("REMOTE")),
         ),
         (
             command_module::pull,
-            Command::new("pull").about("Pull changes from remote to local."),
+            Command::new("pull").about("Pull changes from remote to local.")
+                .arg(arg!([REMOTE] "Path or file:// URL of the remote, remembered if none is set"))
+                .arg(arg!(--"set-remote" "Replace the remembered remote with REMOTE").requires("REMOTE")),
         ),
         (
             command_module::fetch,
-            Command::new("fetch").about("Check remote for new changes."),
+            Command::new("fetch").about("Check remote for new changes.")
+                .arg(arg!([REMOTE] "Path or file:// URL of the remote, remembered if none is set"))
+                .arg(arg!(--"set-remote" "Replace the remembered remote with REMOTE").requires("REMOTE")),
         ),
         (
             command_module::branch,
-            Command::new("branch").about("")
+            Command::new("branch").about("List, create, switch to, rename or delete branches.")
+                .arg(arg!([NAME] "Branch to switch to (created if it does not exist)"))
+                .arg(arg!(-c --create "Create the branch without switching to it").requires("NAME"))
+                .arg(arg!(-r --rename <NEW_NAME> "Rename the branch (defaults to the current branch)"))
+                .arg(arg!(-D --delete "Delete the branch").requires("NAME"))
+                .arg(arg!(-f --force "Delete the branch even if it has pending commits").requires("delete"))
+                .arg(arg!(-s --stash "Stash uncommitted changes before switching").requires("NAME"))
+                .group(ArgGroup::new("action").args(["create", "rename", "delete", "stash"]))
         ),
         (
             command_module::stash,
-            Command::new("stash")
-                // pseudo-commits basically
-                // clear stash after a commit
-                // stash create
-                // stash view
-                // stash restore
-                // stash delete
-                .about(""),
+            // pseudo-commits bound to the current branch, cleared after a commit
+            Command::new("stash").about("Stash tracked changes and roll the working tree back, or manage existing stashes.")
+                .arg(arg!([NAME] "Name of the new stash (defaults to 'WIP on {branch}')"))
+                .arg(arg!(-l --list "List stashes"))
+                .arg(arg!(-b --branch <BRANCH> "Branch to list stashes of (defaults to the current branch)").requires("list"))
+                .arg(arg!(-v --view <STASH> "Show a stash (by number or name)"))
+                .arg(arg!(--drop <STASH> "Delete a stash (by number or name)"))
+                .arg(arg!(--clear "Delete all stashes of the current branch"))
+                .group(ArgGroup::new("action").args(["NAME", "list", "view", "drop", "clear"]))
         ),
         (
             command_module::restore,
-            Command::new("restore"), // unimplemented
+            Command::new("restore").about("Apply a stash on top of the working tree.")
+                .arg(arg!([STASH] "Stash to restore, by number or name (defaults to the most recent)"))
+                .arg(arg!(-k --keep "Keep the stash after restoring it"))
         ),
         (
             command_module::rollback,
-            Command::new("rollback").about("Discard all current changes. Rolls back to most recent commit (or pending commit)."),
+            Command::new("rollback").about("Discard all current changes. Rolls back to most recent commit (or pending commit).")
+                .arg(
+                    arg!([PATH]... "Only roll back these file(s) or directories")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    arg!(-p --pending <COMMIT> "Pending commit number to roll back to")
+                        .value_parser(value_parser!(usize))
+                )
+                .arg(arg!(-c --commit <COMMIT> "Commit to roll back to, as any revision").id("COMMIT").conflicts_with("pending"))
+                .arg(arg!(-f --force "Overwrite untracked files")),
         ),
         (
             command_module::cherry,
-            Command::new("cherry").about("Go to specific commit."),
+            Command::new("cherry").about("Go to specific commit.")
+                .arg_required_else_help(true)
+                .arg(arg!([COMMIT] "Commit to check out (id, tag, branch, HEAD~N, pending:N, {branch}@{upstream} or {branch}@{time})"))
+                .arg(
+                    arg!(-p --pending <COMMIT> "Pending commit number to check out")
+                        .value_parser(value_parser!(usize))
+                )
+                .arg(arg!(--tip "Return to the tip of the current branch"))
+                .group(ArgGroup::new("target").args(["COMMIT", "pending", "tip"]).required(true)),
+        ),
+        (
+            command_module::pick,
+            Command::new("pick").about("Apply a commit from any branch onto the current branch as a new pending commit.")
+                .arg_required_else_help(true)
+                .arg(arg!([COMMIT] "Commit to pick (id, tag, branch, HEAD~N, pending:N, {branch}@{upstream} or {branch}@{time})"))
+                .arg(
+                    arg!(-p --pending <COMMIT> "Pending commit number to pick")
+                        .value_parser(value_parser!(usize))
+                )
+                .arg(arg!(-b --branch <BRANCH> "Branch to pick the commit from (defaults to the current branch)"))
+                .group(ArgGroup::new("target").args(["COMMIT", "pending"]).required(true)),
+        ),
+        (
+            command_module::revert,
+            Command::new("revert").about("Undo an earlier commit with a new pending commit.")
+                .arg_required_else_help(true)
+                .arg(arg!([COMMIT] "Commit to revert (id, tag, branch, HEAD~N, pending:N, {branch}@{upstream} or {branch}@{time})"))
+                .arg(
+                    arg!(-p --pending <COMMIT> "Pending commit number to revert")
+                        .value_parser(value_parser!(usize))
+                )
+                .group(ArgGroup::new("target").args(["COMMIT", "pending"]).required(true)),
         ),
         (
             command_module::tree,
             Command::new("tree").about("Generate content tree of current directory."),
         ),
+        (
+            command_module::status,
+            Command::new("status").about("View tracked, deleted and untracked changes in the working tree.")
+                .arg(arg!(-s --short "Show one path per line, prefixed with its state (M, A, D, ??, !!)"))
+                .arg(arg!(--ignored "Show ignored files as well"))
+        ),
         (
             command_module::staging,
-            Command::new("staging").about("View all staging changes."),
+            Command::new("staging").about("View all staged changes."),
+        ),
+        (
+            command_module::grep,
+            Command::new("grep").about("Search the tracked files of the working tree, upstream or any commit for a line of text.")
+                .arg(arg!(<PATTERN> "Text to search for"))
+                .arg(arg!([COMMIT] "Search the files as of this commit, as any revision (defaults to the working tree)"))
+                .arg(arg!(-u --upstream "Search the files upstream, including every pending commit").conflicts_with("COMMIT"))
+                .arg(arg!(-i --"ignore-case" "Ignore case when matching"))
+                .arg(arg!(-l --"files-with-matches" "Only list the files containing a match"))
+                .arg(
+                    arg!(<PATH>... "Only search these file(s) or directories")
+                        .required(false)
+                        .last(true)
+                        .value_parser(value_parser!(PathBuf)),
+                )
+        ),
+        (
+            command_module::log,
+            Command::new("log").about("View pending and committed history, most recent first.")
+                .arg(
+                    arg!([PATH]... "Only show commits affecting these file(s) or directories, following renames")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(arg!(--author <AUTHOR> "Only show commits by this author"))
+                .arg(arg!(--since <DATE> "Only show commits made on or after this date (YYYY-MM-DD [HH:MM[:SS]])"))
+                .arg(arg!(--until <DATE> "Only show commits made on or before this date (YYYY-MM-DD [HH:MM[:SS]])"))
+                .arg(arg!(--grep <PATTERN> "Only show commits whose message or description contains this text"))
+                .arg(arg!(-S <TEXT> "Only show commits that add or remove occurrences of this text").id("pickaxe"))
+                .arg(arg!(--oneline "Show each commit on a single line"))
+                .arg(arg!(--stat "Show the number of added and deleted lines per file"))
+                .arg(arg!(-p --patch "Show the line changes of each commit, only for the given paths if any"))
+                .arg(arg!(--"no-color" "Disable coloured output"))
+                .arg(arg!(--format <FORMAT> "Custom format, using {id}, {time}, {message}, {description}, {author} and {files}").conflicts_with("oneline"))
+                .arg(
+                    arg!(-L <RANGE> "Follow lines START,END:PATH back through history, eg : 40,80:src/core/state.rs")
+                        .id("lines")
+                        .conflicts_with_all(["PATH", "stat", "oneline", "format", "patch"])
+                )
+        ),
+        (
+            command_module::tag,
+            Command::new("tag").about("List, create or delete tags.")
+                .arg(arg!([NAME] "Name of the new tag"))
+                .arg(arg!([COMMIT] "Commit to tag, as any revision (defaults to HEAD)").requires("NAME"))
+                .arg(
+                    arg!(-p --pending <COMMIT> "Pending commit number to tag")
+                        .value_parser(value_parser!(usize))
+                        .requires("NAME")
+                        .conflicts_with("COMMIT")
+                )
+                .arg(arg!(-m --message <MESSAGE> "Create an annotated tag with this message").requires("NAME"))
+                .arg(arg!(-d --delete "Delete the tag").requires("NAME").conflicts_with_all(["COMMIT", "pending", "message"]))
+        ),
+        (
+            command_module::bisect,
+            Command::new("bisect").about("Binary search the history of the current branch for the commit that introduced a bug.")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("start").about("Start a bisect, optionally marking the bad and good commits.")
+                        .arg(arg!([BAD] "Bad commit, as any revision"))
+                        .arg(arg!([GOOD]... "Good commit(s), as any revision"))
+                )
+                .subcommand(
+                    Command::new("good").about("Mark a commit as good.")
+                        .arg(arg!([COMMIT] "Commit, as any revision (defaults to the checked out commit)"))
+                )
+                .subcommand(
+                    Command::new("bad").about("Mark a commit as bad.")
+                        .arg(arg!([COMMIT] "Commit, as any revision (defaults to the checked out commit)"))
+                )
+                .subcommand(
+                    Command::new("skip").about("Skip a commit that cannot be tested.")
+                        .arg(arg!([COMMIT] "Commit, as any revision (defaults to the checked out commit)"))
+                )
+                .subcommand(Command::new("reset").about("Stop bisecting, and return to the commit checked out before starting."))
+                .subcommand(
+                    Command::new("run").about("Test commits automatically. Exit code 0 is good, 125 is skip, any other code below 128 is bad.")
+                        .arg(
+                            arg!(<COMMAND>... "Command to run, along with its arguments")
+                                .trailing_var_arg(true)
+                                .allow_hyphen_values(true)
+                        )
+                )
+        ),
+        (
+            command_module::blame,
+            Command::new("blame").about("Show the commit that last changed each line of a file.")
+                .arg(arg!(<PATH> "File to blame").value_parser(value_parser!(PathBuf)))
+                .arg(arg!([COMMIT] "Blame the file as of this commit, as any revision (defaults to the working tree)"))
+                .arg(arg!(-L <RANGE> "Only blame lines START,END (1-based, inclusive)").id("range"))
+        ),
+        (
+            command_module::show,
+            Command::new("show").about("View a single commit, with its affected tree and line changes.")
+                .arg_required_else_help(true)
+                .arg(arg!([COMMIT] "Commit to show (id, tag, branch, HEAD~N, pending:N, {branch}@{upstream} or {branch}@{time})"))
+                .arg(
+                    arg!(-p --pending <COMMIT> "Pending commit number to show")
+                        .value_parser(value_parser!(usize))
+                )
+                .arg(arg!(--"no-color" "Disable coloured output"))
+                .group(ArgGroup::new("target").args(["COMMIT", "pending"]).required(true)),
         ),
         (
             command_module::pending,
-            Command::new("pending").about("View all pending commits.")
-                .arg(arg!([COMMIT]... "Commit number."))
+            Command::new("pending").about("View all pending commits, or rewrite them.")
+                .arg(arg!([COMMIT] "Pending commit to view, by number or any revision."))
+                .args_conflicts_with_subcommands(true)
+                .subcommand(
+                    Command::new("reword").about("Change the message or description of a pending commit.")
+                        .arg(arg!(<COMMIT> "Pending commit, by number or any revision."))
+                        .arg(arg!(-m --message <MESSAGE> "Commit message"))
+                        .arg(arg!(-d --description <DESCRIPTION> "Commit description"))
+                        .group(ArgGroup::new("text").args(["message", "description"]).required(true).multiple(true))
+                )
+                .subcommand(
+                    Command::new("drop").about("Remove a pending commit, undoing its changes.")
+                        .arg(arg!(<COMMIT> "Pending commit, by number or any revision."))
+                )
+                .subcommand(
+                    Command::new("move").about("Move a pending commit to another position in the queue.")
+                        .arg(arg!(<COMMIT> "Pending commit, by number or any revision."))
+                        .arg(arg!(<TO> "New commit number.").value_parser(value_parser!(usize)))
+                )
+                .subcommand(
+                    Command::new("split").about("Split the changes at the given paths off a pending commit, into a commit right before it.")
+                        .arg(arg!(<COMMIT> "Pending commit, by number or any revision."))
+                        .arg(arg!(-m --message <MESSAGE> "Message of the split off commit").required(true))
+                        .arg(arg!(--"rest-message" <MESSAGE> "Message of the remaining commit (defaults to the original message)"))
+                        .arg(
+                            arg!(<PATH>... "File(s) or directories to split off")
+                                .last(true)
+                                .value_parser(value_parser!(PathBuf)),
+                        )
+                )
+                .subcommand(
+                    Command::new("squash").about("Fold a pending commit into the one before it.")
+                        .arg(arg!(<COMMIT> "Pending commit, by number or any revision."))
+                )
+        ),
+        (
+            command_module::squash,
+            Command::new("squash").about("Squash pending commits into a single commit.")
+                .arg(
+                    arg!([COUNT] "Number of most recent pending commits to squash (defaults to all)")
+                        .value_parser(value_parser!(usize))
+                )
+                .arg(arg!(-m --message <MESSAGE> "Commit message (defaults to the oldest squashed commit's)"))
+                .arg(arg!(-d --description <DESCRIPTION> "Commit description"))
         ),
         (
             command_module::qhar,
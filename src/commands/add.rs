@@ -1,10 +1,15 @@
-use std::{collections::HashSet, fs, path::PathBuf};
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+};
 
 use clap::ArgMatches;
 
-use crate::core::{paths::RELIC_PATH_TRACKED, state::State};
+use crate::core::{index::Selection, modifications, paths::RELIC_PATH_TRACKED, state::State};
 
-pub fn add(_: &mut State, args: &ArgMatches) {
+pub fn add(state: &mut State, args: &ArgMatches) {
     let f = args
         .get_many::<PathBuf>("FILE")
         .unwrap()
@@ -19,7 +24,7 @@ pub fn add(_: &mut State, args: &ArgMatches) {
             .map(|x| x.to_string())
             .collect::<Vec<String>>(),
     );
-    for p in f {
+    for p in &f {
         // TODO : path.join for this? or concatenating / works?
         result.insert(format!(
             "{}{}",
@@ -35,4 +40,96 @@ pub fn add(_: &mut State, args: &ArgMatches) {
         format!("./{RELIC_PATH_TRACKED}"),
         result.drain().collect::<Vec<String>>().join("\n"),
     );
+
+    // stage a snapshot of the current content
+    let result = if let Some(selection) = args.get_one::<String>("hunks") {
+        // 1-indexed, counted across all files
+        let Some(selection) = selection
+            .split(",")
+            .map(|x| x.trim().parse::<usize>().ok())
+            .collect::<Option<HashSet<usize>>>()
+        else {
+            println!(
+                "Invalid hunk selection '{selection}'. Hunks are selected by number, eg : 1,3"
+            );
+            return;
+        };
+        let mut index = 0;
+        state.index_add_hunks(&f, |_, _| {
+            index += 1;
+            selection.contains(&index)
+        })
+    } else if args.get_flag("patch") {
+        let mut index = 0;
+        let mut remaining_in: Option<(PathBuf, bool)> = None;
+        let mut quit = false;
+        state.index_add_hunks(&f, |path, selection| {
+            index += 1;
+            if quit {
+                return false;
+            }
+            // the rest of a file has already been decided
+            if let Some((p, decision)) = &remaining_in {
+                if p == path {
+                    return *decision;
+                }
+            }
+
+            let what = match selection {
+                Selection::Hunk(hunk) => {
+                    println!("\n| {}\n{}", path.display(), hunk.header(0));
+                    for l in &hunk.deleted {
+                        println!("- {l}");
+                    }
+                    for l in &hunk.inserted {
+                        println!("+ {l}");
+                    }
+                    "hunk"
+                }
+                Selection::Tree(c_mod) => {
+                    let what = match c_mod {
+                        modifications::Tree::CreateBlob(_, _) => "creation of the file",
+                        modifications::Tree::DeleteBlob(_, _) => "deletion of the file",
+                        modifications::Tree::CreateTree(_, _) => "creation of the directory",
+                        modifications::Tree::DeleteTree(_, _) => "deletion of the directory",
+                    };
+                    println!("\n| {}", path.display());
+                    what
+                }
+            };
+            loop {
+                print!("({index}) Stage this {what} [y,n,q,a,d,?]? ");
+                let _ = io::stdout().flush();
+                let mut answer = String::new();
+                // end of input is taken as quitting
+                if io::stdin().read_line(&mut answer).unwrap_or(0) == 0 {
+                    println!();
+                    quit = true;
+                    return false;
+                }
+                match answer.trim() {
+                    "y" => return true,
+                    "n" => return false,
+                    "q" => {
+                        quit = true;
+                        return false;
+                    }
+                    "a" | "d" => {
+                        let decision = answer.trim() == "a";
+                        remaining_in = Some((path.to_path_buf(), decision));
+                        return decision;
+                    }
+                    _ => println!(
+                        "y - stage this {what}\nn - do not stage this {what}\nq - quit, do not stage this {what} or any remaining changes\na - stage this {what} and all remaining changes at this path\nd - do not stage this {what} or any remaining changes at this path"
+                    ),
+                }
+            }
+        })
+    } else {
+        state.index_add(&f)
+    };
+
+    if let Err(e) = result {
+        println!("Unable to stage changes : {e:?}");
+    }
 }
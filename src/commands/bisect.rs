@@ -0,0 +1,174 @@
+use std::process;
+
+use clap::ArgMatches;
+
+use crate::core::{
+    bisect::{BisectStep, Verdict},
+    commit::Commit,
+    error::RelicError,
+    state::State,
+};
+
+use super::messages::common;
+use super::pick::resolve_current;
+
+pub fn bisect(state: &mut State, args: &ArgMatches) {
+    let (operation, sub_args) = args.subcommand().unwrap();
+    let result = match operation {
+        "start" => {
+            let Some(bad) = sub_args
+                .get_one::<String>("BAD")
+                .map(|r| resolve_current(state, r))
+                .map_or(Some(None), |c| c.map(Some))
+            else {
+                return;
+            };
+            let Some(good) = sub_args
+                .get_many::<String>("GOOD")
+                .map_or(vec![], |g| g.collect())
+                .into_iter()
+                .map(|r| resolve_current(state, r))
+                .collect::<Option<Vec<Commit>>>()
+            else {
+                return;
+            };
+            state.bisect_start(bad.as_ref(), &good)
+        }
+        "good" | "bad" | "skip" => {
+            // the checked out commit by default
+            let commit = match sub_args.get_one::<String>("COMMIT") {
+                Some(r) => resolve_current(state, r),
+                None => state.head_commit(),
+            };
+            let Some(commit) = commit else {
+                return;
+            };
+            let verdict = match operation {
+                "good" => Verdict::Good,
+                "bad" => Verdict::Bad,
+                _ => Verdict::Skip,
+            };
+            state.bisect_mark(&commit, verdict)
+        }
+        "reset" => match state.bisect_reset() {
+            Ok(_) => {
+                println!("Bisect reset.");
+                return;
+            }
+            Err(e) => Err(e),
+        },
+        "run" => {
+            let command = sub_args
+                .get_many::<String>("COMMAND")
+                .unwrap()
+                .cloned()
+                .collect::<Vec<String>>();
+            run(state, &command);
+            return;
+        }
+        _ => unimplemented!("Relic Error, bisect operation not defined."),
+    };
+
+    match result {
+        Ok(step) => print_step(&step),
+        Err(e) => report(e),
+    }
+}
+
+fn run(state: &mut State, command: &[String]) {
+    // exit code 0 is good, 125 is skip, any other code below 128 is bad
+    let mut step = match state.bisect_next() {
+        Ok(s) => s,
+        Err(e) => {
+            report(e);
+            return;
+        }
+    };
+
+    loop {
+        print_step(&step);
+        let BisectStep::Testing(commit, _) = &step else {
+            return;
+        };
+
+        let status = match process::Command::new(&command[0])
+            .args(&command[1..])
+            .status()
+        {
+            Ok(s) => s,
+            Err(e) => {
+                println!("Unable to run '{}' : {e}", command.join(" "));
+                return;
+            }
+        };
+        let verdict = match status.code() {
+            Some(0) => Verdict::Good,
+            Some(125) => Verdict::Skip,
+            Some(c) if c < 128 => Verdict::Bad,
+            _ => {
+                println!(
+                    "'{}' was interrupted, bisect run stopped.",
+                    command.join(" ")
+                );
+                return;
+            }
+        };
+
+        step = match state.bisect_mark(commit, verdict) {
+            Ok(s) => s,
+            Err(e) => {
+                report(e);
+                return;
+            }
+        };
+    }
+}
+
+fn print_step(step: &BisectStep) {
+    match step {
+        BisectStep::Waiting => {
+            println!("Mark a good and a bad commit with 'relic bisect good' and 'relic bisect bad' to start searching.")
+        }
+        BisectStep::Testing(commit, left) => println!(
+            "Checked out {} {}\n    {left} commit(s) left to test (roughly {} step(s)).",
+            commit.id_string(),
+            commit.header(),
+            usize::BITS - left.leading_zeros()
+        ),
+        BisectStep::Found(commit) => println!(
+            "The first bad commit is {} {}\nUse 'relic bisect reset' to return to where you started.",
+            commit.id_string(),
+            commit.header()
+        ),
+        BisectStep::Ambiguous(commits) => println!(
+            "Some commits were skipped, the first bad commit is one of:\n{}",
+            commits
+                .iter()
+                .map(|c| format!("    {} {}", c.id_string(), c.header()))
+                .collect::<Vec<String>>()
+                .join("\n")
+        ),
+    }
+}
+
+fn report(e: RelicError) {
+    if common(&e, "checking out") {
+        return;
+    }
+    match e {
+        RelicError::BisectStarted => {
+            println!("A bisect is already in progress. Use 'relic bisect reset' to stop it first.")
+        }
+        RelicError::BisectNotStarted => {
+            println!("No bisect in progress. Use 'relic bisect start' to begin one.")
+        }
+        RelicError::BisectOrder => {
+            println!("Every good commit comes after the bad commit, there is nothing to search.")
+        }
+        RelicError::CommitNotFound => println!("A marked commit no longer exists."),
+        RelicError::UncommittedChanges => {
+            println!("You have uncommitted changes. Roll them back or stash them first.")
+        }
+        e => println!("Unable to complete bisect operation : {e:?}"),
+    }
+}
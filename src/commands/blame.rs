@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+use clap::ArgMatches;
+
+use crate::core::{error::RelicError, state::State, utils};
+
+use super::pick::select_current;
+
+pub fn blame(state: &mut State, args: &ArgMatches) {
+    let path = args.get_one::<PathBuf>("PATH").unwrap();
+
+    // the working tree by default
+    let commit = if args.contains_id("COMMIT") {
+        match select_current(state, args) {
+            Some(c) => Some(c),
+            None => return,
+        }
+    } else {
+        None
+    };
+
+    let lines = match state.blame(path, commit.as_ref()) {
+        Ok(l) => l,
+        Err(RelicError::PathNotFound) => {
+            println!("'{}' does not exist at that commit.", path.display());
+            return;
+        }
+        Err(e) => {
+            println!("Unable to blame '{}' : {e:?}", path.display());
+            return;
+        }
+    };
+
+    // 1-based and inclusive, either end may be left out, eg : "10,20", "10,", ",20"
+    let (start, end) = match args.get_one::<String>("range") {
+        Some(range) => match parse_range(range, lines.len()) {
+            Some(r) => r,
+            None => {
+                println!(
+                    "Invalid line range '{range}', use START,END within 1-{}.",
+                    lines.len()
+                );
+                return;
+            }
+        },
+        None => (1, lines.len()),
+    };
+
+    // pending commits are referred to as they would be in a revision
+    let pending = state.pending_get();
+    let width = lines.len().to_string().len();
+    for (number, line) in lines.iter().enumerate().take(end).skip(start - 1) {
+        let (id, author, time) = match &line.commit {
+            Some(c) => (
+                match pending.iter().position(|p| p.timestamp == c.timestamp) {
+                    Some(i) if c.id.is_none() => format!("pending:{i}"),
+                    _ => c.id_string(),
+                },
+                c.author.clone(),
+                utils::into_human_readable(c.timestamp),
+            ),
+            None => ("uncommitted".to_string(), "-".to_string(), "-".to_string()),
+        };
+        println!(
+            "{id:<11} ({author} {time:<19} {:>width$}) {}",
+            number + 1,
+            line.text
+        );
+    }
+}
+
+fn parse_range(range: &str, length: usize) -> Option<(usize, usize)> {
+    let (start, end) = range.split_once(',')?;
+    let start = if start.is_empty() {
+        1
+    } else {
+        start.trim().parse::<usize>().ok()?
+    };
+    let end = if end.is_empty() {
+        length
+    } else {
+        end.trim().parse::<usize>().ok()?
+    };
+    (1 <= start && start <= end && end <= length).then_some((start, end))
+}
@@ -1,5 +1,101 @@
 use clap::ArgMatches;
 
-use crate::core::state::State;
+use crate::core::{error::RelicError, state::State};
 
-pub fn branch(_: &mut State, _: &ArgMatches) {}
+use super::messages::common;
+
+pub fn branch(state: &mut State, args: &ArgMatches) {
+    let rename = args.get_one::<String>("rename");
+
+    let Some(name) = args.get_one::<String>("NAME") else {
+        if let Some(new_name) = rename {
+            // rename the current branch
+            let current = state.info.branch.clone();
+            match state.branch_rename(&current, new_name) {
+                Ok(_) => println!("Renamed branch '{current}' to '{new_name}'."),
+                Err(e) => report(e, new_name),
+            }
+            return;
+        }
+
+        // list all branches
+        let branches = state.branch_list();
+        let width = branches.iter().map(|b| b.len()).max().unwrap_or(0);
+        for b in branches {
+            println!(
+                "{} {b:<width$} {}",
+                if b == state.info.branch { "*" } else { " " },
+                state
+                    .branch_head(&b)
+                    .map_or("(no commits)".to_string(), |c| c.header())
+            );
+        }
+        return;
+    };
+
+    if args.get_flag("delete") {
+        match state.branch_delete(name, args.get_flag("force")) {
+            Ok(_) => println!("Deleted branch '{name}'."),
+            Err(e) => report(e, name),
+        }
+    } else if let Some(new_name) = rename {
+        match state.branch_rename(name, new_name) {
+            Ok(_) => println!("Renamed branch '{name}' to '{new_name}'."),
+            Err(RelicError::BranchExists) => report(RelicError::BranchExists, new_name),
+            Err(e) => report(e, name),
+        }
+    } else if args.get_flag("create") {
+        match state.branch_create(name) {
+            Ok(_) => println!("Created branch '{name}'."),
+            Err(e) => report(e, name),
+        }
+    } else {
+        // switch, create if it doesnt exist yet
+        if name == &state.info.branch {
+            println!("Already on branch '{name}'.");
+            return;
+        }
+        if !state.branch_exists(name) {
+            if let Err(e) = state.branch_create(name) {
+                report(e, name);
+                return;
+            }
+            println!("Created branch '{name}'.");
+        }
+        if args.get_flag("stash") && !state.get_tracked_changes().is_empty() {
+            // stashes stay with the branch they were made on
+            match state.stash_create(None) {
+                Ok(c) => println!("Stashed changes as \"{}\".", c.message),
+                Err(e) => {
+                    report(e, name);
+                    return;
+                }
+            }
+        }
+        match state.branch_switch(name) {
+            Ok(_) => println!("Switched to branch '{name}'."),
+            Err(e) => report(e, name),
+        }
+    }
+}
+
+fn report(e: RelicError, name: &str) {
+    if common(&e, "switching branches") {
+        return;
+    }
+    match e {
+        RelicError::BranchNotFound => println!("Branch '{name}' does not exist."),
+        RelicError::BranchExists => println!("Branch '{name}' already exists."),
+        RelicError::BranchNameInvalid => println!("'{name}' is not a valid branch name."),
+        RelicError::BranchCurrent => {
+            println!("Cannot delete the current branch '{name}'. Switch to another branch first.")
+        }
+        RelicError::BranchHasPending => println!(
+            "Branch '{name}' has pending commits that have not been pushed. Use --force to delete it anyway."
+        ),
+        RelicError::UncommittedChanges => {
+            println!("You have uncommitted changes. Commit them, or use --stash to stash them before switching branches.")
+        }
+        e => println!("Unable to complete branch operation : {e:?}"),
+    }
+}
@@ -1,5 +1,53 @@
 use clap::ArgMatches;
 
-use crate::core::state::State;
+use crate::core::{error::RelicError, state::State};
 
-pub fn cherry(_: &mut State, _: &ArgMatches) {}
+use super::messages::common;
+use super::pick::select_current;
+
+pub fn cherry(state: &mut State, args: &ArgMatches) {
+    if args.get_flag("tip") {
+        if state.info.detached.is_none() {
+            println!("Already at the tip of branch '{}'.", state.info.branch);
+            return;
+        }
+        match state.cherry_tip() {
+            Ok(_) => println!("Returned to the tip of branch '{}'.", state.info.branch),
+            Err(e) => report(e),
+        }
+        return;
+    }
+
+    let Some(commit) = select_current(state, args) else {
+        return;
+    };
+
+    // checking out the tip itself is the same as returning to it
+    if state
+        .branch_head(&state.info.branch)
+        .is_some_and(|c| c.timestamp == commit.timestamp)
+    {
+        match state.cherry_tip() {
+            Ok(_) => println!("Returned to the tip of branch '{}'.", state.info.branch),
+            Err(e) => report(e),
+        }
+        return;
+    }
+
+    match state.cherry(&commit) {
+        Ok(_) => println!("Checked out {}", commit.header()),
+        Err(e) => report(e),
+    }
+}
+
+fn report(e: RelicError) {
+    if common(&e, "checking out") {
+        return;
+    }
+    match e {
+        RelicError::UncommittedChanges => {
+            println!("You have uncommitted changes. Roll them back or stash them first.")
+        }
+        e => println!("Unable to check out commit : {e:?}"),
+    }
+}
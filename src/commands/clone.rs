@@ -1,24 +1,32 @@
-use clap::ArgMatches;
+use std::path::PathBuf;
 
-use crate::core::State;
+use clap::ArgMatches;
 
-pub fn clone(_: &mut State, args: &ArgMatches) {
-    if let Some(remote) = args.get_one::<String>("URL") {
-        println!("remote : {remote}");
+use crate::core::{remote::remote_root, State};
 
-        // validate if remote is a relic repository
-        // probably need some versioning system
+use super::fetch::report;
 
-        // let _ = fs::create_dir(paths::RELIC_PATH_PARENT);
-        // let _ = fs::create_dir(paths::RELIC_PATH_HISTORY);
-        // let _ = fs::create_dir(paths::RELIC_PATH_PENDING);
-        // let _ = fs::write(paths::RELIC_PATH_INFO, DEFAULT_INFO);
-        // let _ = fs::write(paths::RELIC_PATH_ROOT, "");
-        // let _ = fs::write(paths::RELIC_PATH_TRACKED, "");
-        // let _ = fs::write(paths::RELIC_PATH_UPSTREAM, DEFAULT_UPSTREAM);
+pub fn clone(_: &mut State, args: &ArgMatches) {
+    let url = args.get_one::<String>("URL").unwrap();
+    let directory = match args.get_one::<PathBuf>("DIRECTORY") {
+        Some(d) => d.clone(),
+        // named after the remote, like the repository it came from
+        None => match remote_root(url) {
+            Ok(root) => PathBuf::from(root.file_name().unwrap_or_default()),
+            Err(e) => {
+                report(e);
+                return;
+            }
+        },
+    };
 
-        // let _ = fs::write(paths::RELIC_PATH_IGNORE, content_set::DEFAULT_IGNORE);
-    } else {
-        println!("No remote URL provided.");
+    match State::clone_into(url, &directory) {
+        Ok(state) => println!(
+            "Cloned '{}' into '{}', on branch '{}'.",
+            state.info.remote,
+            directory.display(),
+            state.info.branch
+        ),
+        Err(e) => report(e),
     }
 }
@@ -1,6 +1,8 @@
+use std::path::PathBuf;
+
 use clap::ArgMatches;
 
-use crate::core::{commit::Commit, state::State, utils};
+use crate::core::{error::RelicError, state::State};
 
 pub fn commit(state: &mut State, args: &ArgMatches) {
     // push into pending stage
@@ -18,21 +20,52 @@ pub fn commit(state: &mut State, args: &ArgMatches) {
 - 7
 | "lorem/ipsum/saturn/txt"
 + 4 lsdfljs"#;
+    if args.get_flag("amend") {
+        match state.pending_amend(
+            args.get_one::<String>("message").cloned(),
+            args.get_one::<String>("description").cloned(),
+        ) {
+            Ok(c) => println!("Amended {}", c.header()),
+            Err(RelicError::CommitNotFound) => println!("There are no pending commits to amend."),
+            Err(RelicError::NoChanges) => println!("Nothing to amend. Use 'relic add' to stage changes."),
+            Err(RelicError::Detached) => println!("Unable to amend while a previous commit is checked out. Use 'relic cherry --tip' to return to the branch tip."),
+            Err(e) => println!("Unable to amend : {e:?}"),
+        }
+        return;
+    }
+
     let message = args.get_one::<String>("message").unwrap().clone();
     let description = args
         .get_one::<String>("description")
         .map_or("".to_string(), String::clone);
+    let paths = args
+        .get_many::<PathBuf>("PATH")
+        .map(|p| p.cloned().collect::<Vec<PathBuf>>());
 
-    let commit = Commit {
-        id: None,
+    match state.commit_create(
         message,
         description,
-        change: state.get_changes(),
-        timestamp: utils::get_time(),
-        author: "no_one".to_string(),
-    };
+        paths.as_deref(),
+        args.get_flag("allow-empty"),
+    ) {
+        Ok(_) => {}
+        Err(RelicError::Detached) => {
+            println!("Unable to commit while a previous commit is checked out. Use 'relic cherry --tip' to return to the branch tip.");
+            return;
+        }
+        Err(RelicError::NoChanges) => {
+            println!("Nothing to commit. Use 'relic add' to stage changes, or --allow-empty to commit anyway.");
+            return;
+        }
+        Err(e) => {
+            println!("Unable to commit : {e:?}");
+            return;
+        }
+    }
 
-    state.pending_add(commit);
-    // update upstream
-    (*state).update_upstream(&mut state.track_set.clone());
+    // stashes are made against the previous upstream
+    let cleared = state.stash_clear();
+    if cleared > 0 {
+        println!("Cleared {cleared} stash(es).");
+    }
 }
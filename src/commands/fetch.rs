@@ -1,5 +1,90 @@
 use clap::ArgMatches;
 
-use crate::core::state::State;
+use crate::core::{error::RelicError, remote::remote_root, state::State};
 
-pub fn fetch(_: &mut State, _: &ArgMatches) {}
+use super::messages::{common, indented};
+
+pub fn fetch(state: &mut State, args: &ArgMatches) {
+    if !remote(state, args) {
+        return;
+    }
+
+    match state.fetch() {
+        Ok(branches) => {
+            for (name, new) in branches {
+                match new {
+                    0 => println!("'{name}' is up to date."),
+                    1 => println!("'{name}' has 1 new commit."),
+                    n => println!("'{name}' has {n} new commits."),
+                }
+            }
+        }
+        Err(e) => report(e),
+    }
+}
+
+pub fn remote(state: &mut State, args: &ArgMatches) -> bool {
+    // a remote given on the command line is remembered if none is set yet, or with --set-remote
+    let Some(url) = args.get_one::<String>("REMOTE") else {
+        return true;
+    };
+    let root = match remote_root(url) {
+        Ok(root) => root.to_string_lossy().to_string(),
+        Err(e) => {
+            report(e);
+            return false;
+        }
+    };
+    if root == state.info.remote {
+        return true;
+    }
+    if !state.info.remote.is_empty() && !args.get_flag("set-remote") {
+        println!(
+            "The remote is set to '{}'. Use --set-remote to replace it.",
+            state.info.remote
+        );
+        return false;
+    }
+
+    match state.remote_set(url) {
+        Ok(root) => {
+            println!("Remote set to '{root}'.");
+            true
+        }
+        Err(e) => {
+            report(e);
+            false
+        }
+    }
+}
+
+pub fn report(e: RelicError) {
+    if common(&e, "pulling") {
+        return;
+    }
+    match e {
+        RelicError::RemoteNotSet => println!(
+            "No remote is set. Give the path of a Relic repository, eg : relic fetch ../project"
+        ),
+        RelicError::RemoteNotFound => println!("The remote is not a Relic repository."),
+        RelicError::RemoteAhead => {
+            println!("The remote has commits that are not here. Pull them first.")
+        }
+        RelicError::RemoteDiverged => {
+            println!("The history of the remote does not continue the local history.")
+        }
+        RelicError::RemoteBusy => println!(
+            "The remote branch has pending commits or uncommitted changes, it cannot be pushed to."
+        ),
+        RelicError::BranchNotFound => println!("The branch does not exist on the remote."),
+        RelicError::DirectoryNotEmpty => println!("The destination is not an empty directory."),
+        RelicError::UncommittedChanges => {
+            println!("There are uncommitted changes. Commit or stash them first.")
+        }
+        RelicError::Conflicts(conflicts) => println!(
+            "The pending commits cannot be rebased on the remote commits, the following paths conflict:\n{}",
+            indented(conflicts.iter().map(|c| c.describe()))
+        ),
+        e => println!("Unable to reach the remote : {e:?}"),
+    }
+}
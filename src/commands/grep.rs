@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+
+use clap::ArgMatches;
+
+use crate::core::{revision::Revision, state::State};
+
+use super::pick::select;
+
+pub fn grep(state: &mut State, args: &ArgMatches) {
+    let pattern = args.get_one::<String>("PATTERN").unwrap();
+    let paths = args
+        .get_many::<PathBuf>("PATH")
+        .map_or(vec![], |p| p.cloned().collect::<Vec<PathBuf>>());
+
+    // the tracked files of the working tree by default
+    let tree = if args.get_flag("upstream") {
+        Some(state.upstream.clone())
+    } else if args.contains_id("COMMIT") {
+        let Some(Revision { branch, commit }) = select(state, args, &state.info.branch) else {
+            return;
+        };
+        match state.branch_commit_tree(&branch, &commit) {
+            Ok(t) => Some(t),
+            Err(_) => {
+                println!("Unable to reconstruct the tree of branch '{branch}'.");
+                return;
+            }
+        }
+    } else {
+        None
+    };
+
+    let matches = state.grep(pattern, tree.as_ref(), &paths, args.get_flag("ignore-case"));
+    if args.get_flag("files-with-matches") {
+        let mut files = matches.iter().map(|m| &m.path).collect::<Vec<&PathBuf>>();
+        files.dedup();
+        for f in files {
+            println!("{}", f.display());
+        }
+    } else {
+        for m in &matches {
+            println!("{}:{}:{}", m.path.display(), m.line, m.text);
+        }
+    }
+}
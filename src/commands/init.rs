@@ -2,15 +2,19 @@ use std::fs;
 
 use clap::ArgMatches;
 
-use crate::core::{content_set, objects::data::Upstream, paths, RelicInfo, State};
+use crate::core::{content_set, paths, RelicInfo, State};
 
 pub fn init(_: &mut State, _: &ArgMatches) {
     // create
     // .relic
     //      history/ (empty)
     //      pending/ (empty)
+    //      branches/ (empty)
+    //      stash/ (empty)
+    //      tags/ (empty)
     //      root (empty)
     //      tracked (empty)
+    //      index (empty)
     //      upstream (empty)
     // .relic_ignore (use default (const in content_set))
 
@@ -18,13 +22,7 @@ pub fn init(_: &mut State, _: &ArgMatches) {
     // update root
     // update upstream
 
-    let _ = fs::create_dir(paths::RELIC_PATH_PARENT);
-    let _ = fs::create_dir(paths::RELIC_PATH_HISTORY);
-    let _ = fs::create_dir(paths::RELIC_PATH_PENDING);
-    let _ = fs::write(paths::RELIC_PATH_INFO, RelicInfo::default().serialise());
-    let _ = fs::write(paths::RELIC_PATH_ROOT, "");
-    let _ = fs::write(paths::RELIC_PATH_TRACKED, "");
-    let _ = fs::write(paths::RELIC_PATH_UPSTREAM, Upstream::empty().serialise());
+    State::initialise_repository(&RelicInfo::default());
 
     let _ = fs::write(paths::RELIC_PATH_IGNORE, content_set::DEFAULT_IGNORE);
 
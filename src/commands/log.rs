@@ -0,0 +1,187 @@
+use std::{
+    io::{self, IsTerminal},
+    path::PathBuf,
+};
+
+use clap::ArgMatches;
+
+use crate::core::{commit::Commit, error::RelicError, state::State, utils};
+
+use super::show::{print_file, print_hunk, print_hunks};
+
+pub fn log(state: &mut State, args: &ArgMatches) {
+    let mut since = None;
+    let mut until = None;
+    for (arg, bound) in [("since", &mut since), ("until", &mut until)] {
+        if let Some(s) = args.get_one::<String>(arg) {
+            let Some(t) = utils::from_human_readable(s) else {
+                println!("Invalid date '{s}'. Dates are formatted as YYYY-MM-DD, optionally followed by HH:MM[:SS].");
+                return;
+            };
+            *bound = Some(t);
+        }
+    }
+
+    if let Some(lines) = args.get_one::<String>("lines") {
+        line_log(
+            state,
+            lines,
+            io::stdout().is_terminal() && !args.get_flag("no-color"),
+        );
+        return;
+    }
+
+    let mut paths = args
+        .get_many::<PathBuf>("PATH")
+        .map_or(vec![], |p| p.cloned().collect::<Vec<PathBuf>>());
+    let author = args.get_one::<String>("author");
+    let pattern = args.get_one::<String>("grep").map(|p| p.to_lowercase());
+    let pickaxe = args.get_one::<String>("pickaxe");
+
+    let colour = io::stdout().is_terminal() && !args.get_flag("no-color");
+
+    // most recent first
+    let mut commits = state.history_get();
+    commits.append(&mut state.pending_get());
+    commits.reverse();
+
+    // paths are followed back across renames, so they hold the names at the commit being looked at
+    for c in &commits {
+        let renames = c
+            .change
+            .get_renames()
+            .into_iter()
+            .filter(|(_, to)| paths.iter().any(|p| PathBuf::from(".").join(p) == *to))
+            .collect::<Vec<(PathBuf, PathBuf)>>();
+        let change = if paths.is_empty() {
+            c.change.clone()
+        } else {
+            paths.extend(renames.iter().map(|(from, _)| from.clone()));
+            c.change.filter_paths(&paths)
+        };
+        for (_, to) in &renames {
+            paths.retain(|p| PathBuf::from(".").join(p) != *to);
+        }
+
+        if !(author.is_none_or(|a| c.author == *a)
+            && since.is_none_or(|t| c.timestamp >= t)
+            && until.is_none_or(|t| c.timestamp <= t)
+            && pattern.as_ref().is_none_or(|p| {
+                c.message.to_lowercase().contains(p) || c.description.to_lowercase().contains(p)
+            })
+            && pickaxe.is_none_or(|p| change.changes_occurrences(p))
+            && !change.is_empty())
+        {
+            continue;
+        }
+
+        if let Some(f) = args.get_one::<String>("format") {
+            println!("{}", format(f, c));
+        } else if args.get_flag("oneline") {
+            println!("{}", format("{id} {message}", c));
+        } else {
+            println!("{} {}", c.id_string(), c.header());
+            if !c.description.is_empty() {
+                println!("    {}", c.description.replace("\n", "\n    "));
+            }
+            for (from, to) in &renames {
+                println!("    renamed {} -> {}", from.display(), to.display());
+            }
+        }
+
+        if args.get_flag("stat") {
+            let stat = c.change.get_stat();
+            let width = stat.iter().map(|(p, _, _)| p.len()).max().unwrap_or(0);
+            for (path, additions, deletions) in &stat {
+                println!("    {path:<width$} | +{additions} -{deletions}");
+            }
+            println!(
+                "    {} files affected, {} additions, {} deletions\n",
+                stat.len(),
+                stat.iter().map(|s| s.1).sum::<usize>(),
+                stat.iter().map(|s| s.2).sum::<usize>()
+            );
+        }
+
+        if args.get_flag("patch") {
+            // only the hunks of the followed paths
+            print_hunks(&change, colour);
+            println!();
+        }
+    }
+}
+
+fn line_log(state: &State, lines: &str, colour: bool) {
+    // START,END:PATH, 1-based and inclusive
+    let parsed = lines.split_once(':').and_then(|(range, path)| {
+        let (start, end) = range.split_once(',')?;
+        Some((
+            start.trim().parse::<usize>().ok()?.checked_sub(1)?,
+            end.trim().parse::<usize>().ok()?.checked_sub(1)?,
+            PathBuf::from(path),
+        ))
+    });
+    let Some((start, end, path)) = parsed else {
+        println!("Invalid line range '{lines}', use START,END:PATH, eg : 40,80:src/core/state.rs");
+        return;
+    };
+
+    match state.line_history(&path, start, end) {
+        Ok(history) => {
+            for h in history {
+                println!("{} {}", h.commit.id_string(), h.commit.header());
+                println!("    lines {}-{}", h.start + 1, h.end + 1);
+                print_file(&PathBuf::from(".").join(&path), colour);
+                for (offset, hunk) in &h.hunks {
+                    print_hunk(hunk, *offset, colour);
+                }
+                println!();
+            }
+        }
+        Err(RelicError::PathNotFound) => {
+            println!(
+                "'{}' does not exist at the checked out commit.",
+                path.display()
+            )
+        }
+        Err(RelicError::LineRangeInvalid) => println!(
+            "Invalid line range '{lines}', the lines must exist at the checked out commit."
+        ),
+        Err(e) => println!("Unable to follow '{lines}' : {e:?}"),
+    }
+}
+
+fn format(f: &str, c: &Commit) -> String {
+    // {id} {time} {message} {description} {author} {files}
+    // a single pass, so placeholders inside the substituted values are left as they are
+    let mut result = String::new();
+    let mut rest = f;
+    while let Some(next) = rest.chars().next() {
+        if let Some(r) = rest.strip_prefix("\\n") {
+            result.push('\n');
+            rest = r;
+            continue;
+        }
+        if next == '{' {
+            if let Some((name, r)) = rest[1..].split_once('}') {
+                let value = match name {
+                    "id" => Some(c.id_string()),
+                    "time" => Some(utils::into_human_readable(c.timestamp)),
+                    "author" => Some(c.author.clone()),
+                    "files" => Some(c.change.get_affected_blobs().join(", ")),
+                    "description" => Some(c.description.clone()),
+                    "message" => Some(c.message.clone()),
+                    _ => None,
+                };
+                if let Some(value) = value {
+                    result.push_str(&value);
+                    rest = r;
+                    continue;
+                }
+            }
+        }
+        result.push(next);
+        rest = &rest[next.len_utf8()..];
+    }
+    result
+}
@@ -0,0 +1,27 @@
+use crate::core::error::RelicError;
+
+// messages for errors that several commands report the same way
+
+pub fn indented(lines: impl IntoIterator<Item = String>) -> String {
+    lines
+        .into_iter()
+        .map(|l| format!("    {l}"))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn common(e: &RelicError, action: &str) -> bool {
+    // prints the message of a shared error, returns false when the caller has to report it itself
+    // action completes "would be overwritten by ...", eg : "switching branches"
+    match e {
+        RelicError::Detached => println!(
+            "A previous commit is checked out. Use 'relic cherry --tip' to return to the branch tip first."
+        ),
+        RelicError::UntrackedOverwritten(paths) => println!(
+            "The following untracked files would be overwritten by {action}:\n{}",
+            indented(paths.iter().cloned())
+        ),
+        _ => return false,
+    }
+    true
+}
@@ -1,37 +1,58 @@
 pub mod add;
+pub mod bisect;
+pub mod blame;
 pub mod branch;
 pub mod cherry;
 pub mod clone;
 pub mod commit;
 pub mod detach;
 pub mod fetch;
+pub mod grep;
 pub mod init;
+pub mod log;
+pub mod messages;
 pub mod pending;
+pub mod pick;
 pub mod pull;
 pub mod push;
 pub mod qhar;
 pub mod remove;
 pub mod rollback;
+pub mod show;
+pub mod squash;
 pub mod staging;
 pub mod stash;
+pub mod status;
+pub mod tag;
 pub mod test;
 pub mod tree;
+pub mod unstage;
 
 pub use add::add;
+pub use bisect::bisect;
+pub use blame::blame;
 pub use branch::branch;
 pub use cherry::cherry;
 pub use clone::clone;
 pub use commit::commit;
 pub use detach::detach;
 pub use fetch::fetch;
+pub use grep::grep;
 pub use init::init;
+pub use log::log;
 pub use pending::pending;
+pub use pick::{pick, revert};
 pub use pull::pull;
 pub use push::push;
 pub use qhar::qhar;
 pub use remove::remove;
 pub use rollback::rollback;
+pub use show::show;
+pub use squash::squash;
 pub use staging::staging;
 pub use stash::{restore, stash};
+pub use status::status;
+pub use tag::tag;
 pub use test::test;
 pub use tree::tree;
+pub use unstage::unstage;
@@ -1,25 +1,30 @@
+use std::path::PathBuf;
+
 use clap::ArgMatches;
 
-use crate::core::state::State;
+use crate::core::{error::RelicError, state::State};
+
+use super::messages::{common, indented};
 
 pub fn pending(state: &mut State, args: &ArgMatches) {
+    if let Some((operation, sub_args)) = args.subcommand() {
+        rewrite(state, operation, sub_args);
+        return;
+    }
+
     let pending = state.pending_get();
 
-    if let Some(commit_number) = args
-        .get_one::<String>("COMMIT")
-        .map_or(None, |x| x.parse::<i32>().map_or(None, |x| Some(x)))
-    {
+    if let Some(reference) = args.get_one::<String>("COMMIT") {
         // display selected
-        if (commit_number < 0) || (commit_number >= pending.len() as i32) {
-            println!(
+        match state.revision_pending_index(reference) {
+            // if want the blamed tree here, need to refer to previous upstream
+            Ok(index) if index < pending.len() => println!("{}", pending[index].serialise()),
+            Ok(_) => println!(
                 "Invalid selection. Please select commit numbers in the range of (0-{})",
-                pending.len() - 1
-            );
-            return;
+                pending.len().saturating_sub(1)
+            ),
+            Err(_) => println!("'{reference}' is not a pending commit."),
         }
-
-        // if want the blamed tree here, need to refer to previous upstream
-        println!("{}", pending[commit_number as usize].serialise());
     } else {
         // display all
         for (index, c) in pending.iter().enumerate() {
@@ -27,3 +32,82 @@ pub fn pending(state: &mut State, args: &ArgMatches) {
         }
     }
 }
+
+fn rewrite(state: &mut State, operation: &str, args: &ArgMatches) {
+    let reference = args.get_one::<String>("COMMIT").unwrap();
+    let Ok(index) = state.revision_pending_index(reference) else {
+        println!("'{reference}' is not a pending commit.");
+        return;
+    };
+    let result = match operation {
+        "reword" => state
+            .pending_reword(
+                index,
+                args.get_one::<String>("message").cloned(),
+                args.get_one::<String>("description").cloned(),
+            )
+            .map(|c| format!("Reworded {}", c.header())),
+        "drop" => state
+            .pending_drop(index)
+            .map(|c| format!("Dropped {}", c.header())),
+        "move" => {
+            let to = *args.get_one::<usize>("TO").unwrap();
+            state
+                .pending_move(index, to)
+                .map(|_| format!("Moved pending commit {index} to {to}."))
+        }
+        "split" => {
+            let paths = args
+                .get_many::<PathBuf>("PATH")
+                .unwrap()
+                .cloned()
+                .collect::<Vec<PathBuf>>();
+            state
+                .pending_split(
+                    index,
+                    &paths,
+                    args.get_one::<String>("message").unwrap().clone(),
+                    args.get_one::<String>("rest-message").cloned(),
+                )
+                .map(|(first, second)| {
+                    format!(
+                        "Split into\n    {}\n    {}",
+                        first.header(),
+                        second.header()
+                    )
+                })
+        }
+        "squash" => state
+            .pending_squash(index)
+            .map(|c| format!("Squashed into {}", c.header())),
+        _ => unimplemented!("Relic Error, pending operation not defined."),
+    };
+
+    match result {
+        Ok(s) => println!("{s}"),
+        Err(RelicError::CommitNotFound) => println!(
+            "Invalid selection. There are {} pending commits.",
+            state.pending_get().len()
+        ),
+        Err(e) => report(e),
+    }
+}
+
+pub(super) fn report(e: RelicError) {
+    if common(&e, "rewriting the pending commits") {
+        return;
+    }
+    match e {
+        RelicError::NoChanges => println!(
+            "Nothing to split, the paths must match some but not all of the commit's changes."
+        ),
+        RelicError::UncommittedChanges => println!(
+            "You have uncommitted changes, which would be affected by rewriting the pending commits. Commit, roll back or stash them first."
+        ),
+        RelicError::Conflicts(conflicts) => println!(
+            "The pending commits cannot be rewritten, the following paths conflict:\n{}",
+            indented(conflicts.iter().map(|c| c.describe()))
+        ),
+        e => println!("Unable to rewrite pending commits : {e:?}"),
+    }
+}
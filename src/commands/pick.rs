@@ -0,0 +1,105 @@
+use clap::ArgMatches;
+
+use crate::core::{commit::Commit, error::RelicError, revision::Revision, state::State};
+
+use super::messages::{common, indented};
+
+pub fn pick(state: &mut State, args: &ArgMatches) {
+    let branch = args
+        .get_one::<String>("branch")
+        .map_or(state.info.branch.clone(), String::clone);
+    if !state.branch_exists(&branch) {
+        println!("Branch '{branch}' does not exist.");
+        return;
+    }
+
+    let Some(revision) = select(state, args, &branch) else {
+        return;
+    };
+
+    match state.pick(&revision.branch, &revision.commit) {
+        Ok(c) => println!("Picked {}", c.header()),
+        Err(e) => report(e),
+    }
+}
+
+pub fn revert(state: &mut State, args: &ArgMatches) {
+    let Some(commit) = select_current(state, args) else {
+        return;
+    };
+
+    match state.revert(&commit) {
+        Ok(c) => println!("Reverted with {}", c.header()),
+        Err(e) => report(e),
+    }
+}
+
+pub fn select(state: &State, args: &ArgMatches, branch: &str) -> Option<Revision> {
+    resolve(state, branch, &reference(args))
+}
+
+pub fn select_current(state: &State, args: &ArgMatches) -> Option<Commit> {
+    resolve_current(state, &reference(args))
+}
+
+fn reference(args: &ArgMatches) -> String {
+    // COMMIT is any revision, -p N (where a command has it) is short for pending:N
+    match args.try_get_one::<usize>("pending").ok().flatten() {
+        Some(index) => format!("pending:{index}"),
+        None => args.get_one::<String>("COMMIT").unwrap().clone(),
+    }
+}
+
+pub fn resolve(state: &State, branch: &str, reference: &str) -> Option<Revision> {
+    match state.revision_parse_in(branch, reference) {
+        Ok(r) => Some(r),
+        Err(e) => {
+            match e {
+                RelicError::CommitNotFound => println!("Commit '{reference}' does not exist."),
+                RelicError::BranchNotFound => {
+                    println!("'{reference}' refers to a branch that does not exist.")
+                }
+                RelicError::RevisionAmbiguous => println!(
+                    "'{reference}' matches more than one commit, use a longer id."
+                ),
+                _ => println!(
+                    "'{reference}' is not a valid commit reference. Use an id, tag, branch, HEAD~N, pending:N, {{branch}}@{{upstream}} or {{branch}}@{{time}}."
+                ),
+            }
+            None
+        }
+    }
+}
+
+pub fn resolve_current(state: &State, reference: &str) -> Option<Commit> {
+    // for commands that only work on commits of the current branch
+    let revision = resolve(state, &state.info.branch, reference)?;
+    if revision.branch != state.info.branch {
+        println!(
+            "Commit is part of branch '{}', not the current branch '{}'.",
+            revision.branch, state.info.branch
+        );
+        return None;
+    }
+    Some(revision.commit)
+}
+
+fn report(e: RelicError) {
+    if common(&e, "applying the commit") {
+        return;
+    }
+    match e {
+        RelicError::CommitNotFound => println!("Commit does not exist."),
+        RelicError::NoChanges => {
+            println!("Nothing to apply, the commit is already part of this branch.")
+        }
+        RelicError::UncommittedChanges => {
+            println!("You have uncommitted changes. Commit, roll back or stash them first.")
+        }
+        RelicError::Conflicts(conflicts) => println!(
+            "The commit no longer applies, the following paths conflict:\n{}",
+            indented(conflicts.iter().map(|c| c.describe()))
+        ),
+        e => println!("Unable to apply commit : {e:?}"),
+    }
+}
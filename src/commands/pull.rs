@@ -2,4 +2,23 @@ use clap::ArgMatches;
 
 use crate::core::state::State;
 
-pub fn pull(_: &mut State, _: &ArgMatches) {}
+use super::fetch::{remote, report};
+
+pub fn pull(state: &mut State, args: &ArgMatches) {
+    if !remote(state, args) {
+        return;
+    }
+
+    match state.pull() {
+        Ok(commits) if commits.is_empty() => {
+            println!("'{}' is up to date.", state.info.branch)
+        }
+        Ok(commits) => {
+            println!("Pulled {} commit(s) :", commits.len());
+            for c in commits {
+                println!("    {} {}", c.id_string(), c.header());
+            }
+        }
+        Err(e) => report(e),
+    }
+}
@@ -2,4 +2,21 @@ use clap::ArgMatches;
 
 use crate::core::state::State;
 
-pub fn push(_: &mut State, _: &ArgMatches) {}
+use super::fetch::{remote, report};
+
+pub fn push(state: &mut State, args: &ArgMatches) {
+    if !remote(state, args) {
+        return;
+    }
+
+    match state.push() {
+        Ok(commits) if commits.is_empty() => println!("'{}' is up to date.", state.info.branch),
+        Ok(commits) => {
+            println!("Pushed {} commit(s) :", commits.len());
+            for c in commits {
+                println!("    {} {}", c.id_string(), c.header());
+            }
+        }
+        Err(e) => report(e),
+    }
+}
@@ -65,4 +65,9 @@ pub fn remove(s: &mut State, args: &ArgMatches) {
             .collect::<Vec<String>>()
             .join("\n"),
     );
+
+    // untracked content cant stay staged
+    if let Err(e) = s.index_remove(&f) {
+        println!("Unable to unstage changes : {e:?}");
+    }
 }
@@ -1,5 +1,53 @@
+use std::path::PathBuf;
+
 use clap::ArgMatches;
 
-use crate::core::state::State;
+use crate::core::{error::RelicError, state::State};
+
+use super::{messages::common, pick::select_current};
+
+pub fn rollback(state: &mut State, args: &ArgMatches) {
+    let paths = args
+        .get_many::<PathBuf>("PATH")
+        .map_or(vec![], |p| p.cloned().collect::<Vec<PathBuf>>());
+
+    // the checked out commit by default
+    let target = match args.get_one::<usize>("pending") {
+        Some(index) => {
+            let pending = state.pending_get();
+            let Some(commit) = pending.get(*index) else {
+                println!(
+                    "Invalid selection. There are only {} pending commits.",
+                    pending.len()
+                );
+                return;
+            };
+            state.commit_tree(commit)
+        }
+        None if args.contains_id("COMMIT") => match select_current(state, args) {
+            Some(commit) => state.commit_tree(&commit),
+            None => return,
+        },
+        None => state.head_tree(),
+    };
 
-pub fn rollback(_: &mut State, _: &ArgMatches) {}
+    match state.rollback(&target, &paths, args.get_flag("force")) {
+        Ok(change) if change.is_empty() => println!("Nothing to roll back."),
+        Ok(change) => {
+            let mut affected = change
+                .trees
+                .iter()
+                .map(|t| t.extract_data())
+                .chain(change.blobs.iter().map(|b| b.extract_path()))
+                .collect::<Vec<(String, String)>>();
+            affected.sort();
+            affected.dedup();
+            println!("Rolled back {} path(s).", affected.len());
+        }
+        Err(e @ RelicError::UntrackedOverwritten(_)) => {
+            common(&e, "rolling back");
+            println!("Use --force to roll back anyway.");
+        }
+        Err(e) => println!("Unable to roll back : {e:?}"),
+    }
+}
@@ -0,0 +1,93 @@
+use std::{
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+};
+
+use clap::ArgMatches;
+
+use crate::core::{
+    modifications::{self, Change, Hunk},
+    revision::Revision,
+    state::State,
+    utils,
+};
+
+use super::pick::select;
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+pub fn show(state: &mut State, args: &ArgMatches) {
+    let Some(Revision { branch, commit }) = select(state, args, &state.info.branch) else {
+        return;
+    };
+
+    // colours are only used when writing to a terminal
+    let colour = io::stdout().is_terminal() && !args.get_flag("no-color");
+
+    println!("commit {}", commit.id_string());
+    println!("Author: {}", commit.author);
+    println!("Date:   {}", utils::into_human_readable(commit.timestamp));
+    println!("\n    {}", commit.message.replace("\n", "\n    "));
+    if !commit.description.is_empty() {
+        println!("\n    {}", commit.description.replace("\n", "\n    "));
+    }
+
+    let Ok(mut parent) = state.branch_commit_tree(&branch, &commit) else {
+        println!("Unable to reconstruct the tree of branch '{branch}'.");
+        return;
+    };
+    parent.unapply_changes(&commit.change);
+    println!("\n{}\n", commit.change.as_blame_tree(&parent));
+
+    print_hunks(&commit.change, colour);
+}
+
+pub fn print_hunks(change: &Change, colour: bool) {
+    let mut files = change
+        .as_map()
+        .1
+        .into_iter()
+        .flat_map(|(path, names)| {
+            names
+                .into_iter()
+                .map(move |(name, modifications)| (Path::new(&path).join(name), modifications))
+        })
+        .collect::<Vec<(PathBuf, Vec<modifications::Blob>)>>();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (path, modifications) in files {
+        print_file(&path, colour);
+
+        let mut offset: i64 = 0;
+        for h in Hunk::from_modifications(&modifications) {
+            print_hunk(&h, offset, colour);
+            offset += h.inserted.len() as i64 - h.deleted.len() as i64;
+        }
+    }
+}
+
+pub fn print_file(path: &Path, colour: bool) {
+    println!("{}", paint(colour, CYAN, format!("| {}", path.display())));
+}
+
+pub fn print_hunk(hunk: &Hunk, offset: i64, colour: bool) {
+    // offset is the shift in line numbers caused by the hunks before
+    println!("{}", paint(colour, CYAN, hunk.header(offset)));
+    for l in &hunk.deleted {
+        println!("{}", paint(colour, RED, format!("- {l}")));
+    }
+    for l in &hunk.inserted {
+        println!("{}", paint(colour, GREEN, format!("+ {l}")));
+    }
+}
+
+fn paint(colour: bool, c: &str, s: String) -> String {
+    if colour {
+        format!("{c}{s}{RESET}")
+    } else {
+        s
+    }
+}
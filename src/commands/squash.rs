@@ -0,0 +1,36 @@
+use clap::ArgMatches;
+
+use crate::core::state::State;
+
+use super::pending::report;
+
+pub fn squash(state: &mut State, args: &ArgMatches) {
+    let pending = state.pending_get();
+    let count = args
+        .get_one::<usize>("COUNT")
+        .copied()
+        .unwrap_or(pending.len());
+
+    if count > pending.len() {
+        println!(
+            "Invalid selection. There are only {} pending commits.",
+            pending.len()
+        );
+        return;
+    }
+    if count < 2 {
+        println!("Nothing to squash.");
+        return;
+    }
+
+    // only the most recent commits are squashed, into the oldest of them
+    // upstream is unaffected, the squashed commit results in the same tree
+    match state.pending_squash_recent(
+        count,
+        args.get_one::<String>("message").cloned(),
+        args.get_one::<String>("description").cloned(),
+    ) {
+        Ok(_) => println!("Squashed {count} pending commits."),
+        Err(e) => report(e),
+    }
+}
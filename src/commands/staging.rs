@@ -1,15 +1,7 @@
 use clap::ArgMatches;
 
-use crate::core::{content_set::TrackingSet, State};
+use crate::core::State;
 
 pub fn staging(s: &mut State, _: &ArgMatches) {
-    println!(
-        "{}",
-        s.get_changes()
-            .filter_changes(&s.track_set.initialise(&mut s.current))
-            // TODO: filter_changes is removing DeletedTree/DeletedBlob
-            // this is not intended behaviour
-            // repro: delete a file/tree, then view if its shown in `relic staging`
-            .as_human_readable(&s.upstream) // .trees
-    );
+    println!("{}", s.index_get().as_human_readable(&s.upstream));
 }
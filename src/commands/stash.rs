@@ -1,7 +1,91 @@
 use clap::ArgMatches;
 
-use crate::core::State;
+use crate::core::{error::RelicError, state::State};
 
-pub fn stash(_: &mut State, _: &ArgMatches) {}
+use super::messages::{common, indented};
 
-pub fn restore(_: &mut State, _: &ArgMatches) {}
+pub fn stash(state: &mut State, args: &ArgMatches) {
+    if args.get_flag("list") {
+        // list the stashes of a branch, the current one by default
+        let branch = args
+            .get_one::<String>("branch")
+            .map_or(state.info.branch.clone(), String::clone);
+        if !state.branch_exists(&branch) {
+            report(RelicError::BranchNotFound, &branch);
+            return;
+        }
+
+        let stashes = state.stash_get_branch(&branch);
+        if stashes.is_empty() {
+            println!("No stashes on branch '{branch}'.");
+        }
+        for (index, c) in stashes.iter().enumerate() {
+            println!("{index}. {}", c.header());
+        }
+    } else if let Some(selector) = args.get_one::<String>("view") {
+        match state.stash_find(selector) {
+            Ok(c) => println!("{}", c.serialise()),
+            Err(e) => report(e, selector),
+        }
+    } else if let Some(selector) = args.get_one::<String>("drop") {
+        match state.stash_find(selector) {
+            Ok(c) => {
+                state.stash_drop(&c);
+                println!("Dropped stash \"{}\".", c.message);
+            }
+            Err(e) => report(e, selector),
+        }
+    } else if args.get_flag("clear") {
+        println!("Cleared {} stash(es).", state.stash_clear());
+    } else {
+        match state.stash_create(args.get_one::<String>("NAME").cloned()) {
+            Ok(c) => println!("Stashed changes as \"{}\".", c.message),
+            Err(e) => report(e, ""),
+        }
+    }
+}
+
+pub fn restore(state: &mut State, args: &ArgMatches) {
+    // most recent stash by default
+    let commit = match args.get_one::<String>("STASH") {
+        Some(selector) => state.stash_find(selector),
+        None => state.stash_get().pop().ok_or(RelicError::StashNotFound),
+    };
+    let commit = match commit {
+        Ok(c) => c,
+        Err(e) => {
+            report(
+                e,
+                args.get_one::<String>("STASH").map_or("", |s| s.as_str()),
+            );
+            return;
+        }
+    };
+
+    match state.stash_restore(&commit) {
+        Ok(_) => {
+            if !args.get_flag("keep") {
+                state.stash_drop(&commit);
+            }
+            println!("Restored stash \"{}\".", commit.message);
+        }
+        Err(e) => report(e, &commit.message),
+    }
+}
+
+fn report(e: RelicError, selector: &str) {
+    if common(&e, "restoring the stash") {
+        return;
+    }
+    match e {
+        RelicError::StashNotFound if selector.is_empty() => println!("There are no stashes."),
+        RelicError::StashNotFound => println!("Stash '{selector}' does not exist."),
+        RelicError::BranchNotFound => println!("Branch '{selector}' does not exist."),
+        RelicError::NoChanges => println!("No tracked changes to stash."),
+        RelicError::Conflicts(conflicts) => println!(
+            "Unable to restore stash '{selector}', the following paths conflict with the working tree:\n{}",
+            indented(conflicts.iter().map(|c| c.describe()))
+        ),
+        e => println!("Unable to complete stash operation : {e:?}"),
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+
+use clap::ArgMatches;
+
+use crate::core::{state::State, status::Changes};
+
+pub fn status(state: &mut State, args: &ArgMatches) {
+    let status = state.status();
+    let ignored = if args.get_flag("ignored") {
+        state.status_ignored()
+    } else {
+        vec![]
+    };
+
+    if args.get_flag("short") {
+        // porcelain, one path per line prefixed with its staged and unstaged state
+        let mut states: BTreeMap<&String, [char; 2]> = BTreeMap::new();
+        for (column, changes) in [&status.staged, &status.unstaged].iter().enumerate() {
+            for (tag, paths) in [
+                ('M', &changes.modified),
+                ('A', &changes.added),
+                ('D', &changes.deleted),
+            ] {
+                for p in paths {
+                    states.entry(p).or_insert([' ', ' '])[column] = tag;
+                }
+            }
+        }
+        for (p, [x, y]) in states {
+            println!("{x}{y} {p}");
+        }
+        for p in &status.untracked {
+            println!("?? {p}");
+        }
+        for p in &ignored {
+            println!("!! {p}");
+        }
+        return;
+    }
+
+    println!("On branch {}", state.info.branch);
+    if let Some(t) = state.info.detached {
+        if let Some(c) = state.commit_get(t) {
+            println!("Checked out {}", c.header());
+        }
+    }
+
+    for (title, changes) in [
+        ("Changes staged for commit:", &status.staged),
+        ("Changes not staged for commit:", &status.unstaged),
+    ] {
+        if changes.is_empty() {
+            continue;
+        }
+        println!("\n{title}");
+        print_changes(changes);
+    }
+
+    for (title, paths) in [
+        ("Untracked files:", &status.untracked),
+        ("Ignored files:", &ignored),
+    ] {
+        if paths.is_empty() {
+            continue;
+        }
+        println!("\n{title}");
+        for p in paths {
+            println!("    {p}");
+        }
+    }
+
+    if status.staged.is_empty() && status.unstaged.is_empty() && status.untracked.is_empty() {
+        println!("\nNothing to commit, working tree clean.");
+    } else if status.staged.is_empty() {
+        println!("\nNothing staged for commit. Use 'relic add' to stage changes.");
+    }
+}
+
+fn print_changes(changes: &Changes) {
+    for (label, paths) in [
+        ("modified", &changes.modified),
+        ("added", &changes.added),
+        ("deleted", &changes.deleted),
+    ] {
+        for p in paths {
+            println!("    {label:<9}{p}");
+        }
+    }
+}
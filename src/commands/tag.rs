@@ -0,0 +1,82 @@
+use clap::ArgMatches;
+
+use crate::core::{error::RelicError, state::State, utils};
+
+use super::pick::select;
+
+pub fn tag(state: &mut State, args: &ArgMatches) {
+    let Some(name) = args.get_one::<String>("NAME") else {
+        list(state);
+        return;
+    };
+
+    if args.get_flag("delete") {
+        match state.tag_delete(name) {
+            Ok(_) => println!("Deleted tag '{name}'."),
+            Err(e) => report(e, name),
+        }
+        return;
+    }
+
+    let commit = if args.contains_id("COMMIT") || args.contains_id("pending") {
+        select(state, args, &state.info.branch).map(|r| r.commit)
+    } else {
+        let commit = state.head_commit();
+        if commit.is_none() {
+            println!("There are no commits to tag.");
+        }
+        commit
+    };
+    let Some(commit) = commit else {
+        return;
+    };
+
+    match state.tag_create(name, &commit, args.get_one::<String>("message").cloned()) {
+        Ok(_) => println!("Tagged {} as '{name}'.", commit.header()),
+        Err(e) => report(e, name),
+    }
+}
+
+fn list(state: &State) {
+    let tags = state.tag_get();
+    if tags.is_empty() {
+        println!("There are no tags.");
+    }
+
+    let branches = state.branch_list();
+    for t in tags {
+        match branches.iter().find_map(|b| {
+            state
+                .tag_resolve(&t, &state.branch_commits(b))
+                .map(|c| (b, c))
+        }) {
+            Some((b, c)) if *b == state.info.branch => {
+                println!("{} {} {}", t.name, c.id_string(), c.header())
+            }
+            Some((b, c)) => println!(
+                "{} {} {} (on branch '{b}')",
+                t.name,
+                c.id_string(),
+                c.header()
+            ),
+            None => println!("{} (commit no longer exists)", t.name),
+        }
+        if let Some(a) = t.annotation {
+            println!(
+                "    tagged by {} ({}) : {}",
+                a.tagger,
+                utils::into_human_readable(a.timestamp),
+                a.message.replace("\n", "\n    ")
+            );
+        }
+    }
+}
+
+fn report(e: RelicError, name: &str) {
+    match e {
+        RelicError::TagNotFound => println!("Tag '{name}' does not exist."),
+        RelicError::TagExists => println!("Tag '{name}' already exists."),
+        RelicError::TagNameInvalid => println!("'{name}' is not a valid tag name."),
+        e => println!("Unable to complete tag operation : {e:?}"),
+    }
+}
@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+
+use clap::ArgMatches;
+
+use crate::core::state::State;
+
+pub fn unstage(state: &mut State, args: &ArgMatches) {
+    // the paths stay tracked, only the index is reset
+    let f = args
+        .get_many::<PathBuf>("FILE")
+        .unwrap()
+        .cloned()
+        .collect::<Vec<PathBuf>>();
+
+    match state.index_remove(&f) {
+        Ok(change) if change.is_empty() => println!("Nothing staged at the given paths."),
+        Ok(_) => {}
+        Err(e) => println!("Unable to unstage changes : {e:?}"),
+    }
+}
@@ -0,0 +1,153 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{commit::Commit, error::RelicError, paths::RELIC_PATH_BISECT, State};
+
+// progress of a bisection, commits are referred to by their timestamp
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Bisect {
+    pub good: Vec<u64>,
+    pub bad: Option<u64>,
+    pub skipped: Vec<u64>,
+    // the commit checked out before starting, none when at the branch tip
+    pub original: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Good,
+    Bad,
+    Skip,
+}
+
+#[derive(Debug, Clone)]
+pub enum BisectStep {
+    // a good and a bad commit are needed before searching
+    Waiting,
+    // checked out the next commit to test, with the number of commits left to test
+    Testing(Commit, usize),
+    Found(Commit),
+    // skipped commits hide which of these is the first bad one
+    Ambiguous(Vec<Commit>),
+}
+
+impl State {
+    // #region bisect
+    pub fn bisect_get(&self) -> Option<Bisect> {
+        fs::read_to_string(RELIC_PATH_BISECT)
+            .ok()
+            .and_then(|t| serde_json::from_str::<Bisect>(&t).ok())
+    }
+
+    fn bisect_save(bisect: &Bisect) -> Result<(), RelicError> {
+        fs::write(RELIC_PATH_BISECT, serde_json::to_string(bisect).unwrap())
+            .map_err(|_| RelicError::FileCantOpen)
+    }
+
+    pub fn bisect_start(
+        &mut self,
+        bad: Option<&Commit>,
+        good: &[Commit],
+    ) -> Result<BisectStep, RelicError> {
+        if self.bisect_get().is_some() {
+            return Err(RelicError::BisectStarted);
+        }
+
+        State::bisect_save(&Bisect {
+            good: good.iter().map(|c| c.timestamp).collect(),
+            bad: bad.map(|c| c.timestamp),
+            skipped: vec![],
+            original: self.info.detached,
+        })?;
+        self.bisect_next()
+    }
+
+    pub fn bisect_mark(
+        &mut self,
+        commit: &Commit,
+        verdict: Verdict,
+    ) -> Result<BisectStep, RelicError> {
+        let mut bisect = self.bisect_get().ok_or(RelicError::BisectNotStarted)?;
+        match verdict {
+            Verdict::Good => bisect.good.push(commit.timestamp),
+            Verdict::Bad => bisect.bad = Some(commit.timestamp),
+            Verdict::Skip => bisect.skipped.push(commit.timestamp),
+        }
+        State::bisect_save(&bisect)?;
+        self.bisect_next()
+    }
+
+    pub fn bisect_next(&mut self) -> Result<BisectStep, RelicError> {
+        // checks out the commit halfway between the last good and the first bad commit
+        let bisect = self.bisect_get().ok_or(RelicError::BisectNotStarted)?;
+        let commits = self.branch_commits(&self.info.branch);
+        let position = |t: u64| commits.iter().position(|c| c.timestamp == t);
+
+        let Some(bad) = bisect.bad else {
+            return Ok(BisectStep::Waiting);
+        };
+        let bad = position(bad).ok_or(RelicError::CommitNotFound)?;
+        if bisect.good.is_empty() {
+            return Ok(BisectStep::Waiting);
+        }
+        // goods at or after the bad commit contradict it
+        let good = bisect
+            .good
+            .iter()
+            .filter_map(|t| position(*t))
+            .filter(|p| *p < bad)
+            .max()
+            .ok_or(RelicError::BisectOrder)?;
+
+        let candidates = (good + 1..bad)
+            .filter(|p| !bisect.skipped.contains(&commits[*p].timestamp))
+            .collect::<Vec<usize>>();
+        if candidates.is_empty() {
+            return Ok(if (good + 1..bad).next().is_none() {
+                BisectStep::Found(commits[bad].clone())
+            } else {
+                BisectStep::Ambiguous(commits[good + 1..=bad].to_vec())
+            });
+        }
+
+        let next = commits[candidates[candidates.len() / 2]].clone();
+        if self
+            .head_commit()
+            .is_none_or(|c| c.timestamp != next.timestamp)
+        {
+            self.cherry(&next)?;
+        }
+        Ok(BisectStep::Testing(next, candidates.len()))
+    }
+
+    pub fn bisect_retarget(&self, from: u64, to: u64) {
+        // a commit that was folded into another one passes its marks on
+        let Some(mut bisect) = self.bisect_get() else {
+            return;
+        };
+        let retarget = |t: &mut u64| {
+            if *t == from {
+                *t = to;
+            }
+        };
+        bisect.good.iter_mut().for_each(retarget);
+        bisect.skipped.iter_mut().for_each(retarget);
+        bisect.bad.iter_mut().for_each(retarget);
+        bisect.original.iter_mut().for_each(retarget);
+        let _ = State::bisect_save(&bisect);
+    }
+
+    pub fn bisect_reset(&mut self) -> Result<(), RelicError> {
+        // returns to the commit checked out before starting
+        let bisect = self.bisect_get().ok_or(RelicError::BisectNotStarted)?;
+        match bisect.original.and_then(|t| self.commit_get(t)) {
+            Some(c) if self.info.detached != Some(c.timestamp) => self.cherry(&c)?,
+            Some(_) => {}
+            None if self.info.detached.is_some() => self.cherry_tip()?,
+            None => {}
+        }
+        fs::remove_file(RELIC_PATH_BISECT).map_err(|_| RelicError::FileCantOpen)
+    }
+    // #endregion
+}
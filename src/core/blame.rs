@@ -0,0 +1,224 @@
+use std::path::{Path, PathBuf};
+
+use crate::core::{
+    commit::Commit,
+    error::RelicError,
+    modifications::{self, Change, Hunk},
+    State,
+};
+
+// a line of a file, along with the commit that last changed it
+// lines that are not committed yet have no commit
+#[derive(Debug, Clone)]
+pub struct BlameLine {
+    pub text: String,
+    pub commit: Option<Commit>,
+}
+
+// a commit that changed a range of lines
+// start and end are the range right after the commit, the hunks are (offset, hunk) as shown by relic show
+#[derive(Debug, Clone)]
+pub struct LineHistory {
+    pub commit: Commit,
+    pub start: usize,
+    pub end: usize,
+    pub hunks: Vec<(i64, Hunk)>,
+}
+
+impl State {
+    // #region blame
+    pub fn blame(
+        &self,
+        path: &Path,
+        commit: Option<&Commit>,
+    ) -> Result<Vec<BlameLine>, RelicError> {
+        // replays the commits of the current branch onto the file, up to commit
+        // without a commit, the working tree version of the file is blamed
+        let path = PathBuf::from(".").join(path);
+        let commits = self.branch_commits(&self.info.branch);
+        let end = match commit.cloned().or_else(|| self.head_commit()) {
+            Some(c) => {
+                commits
+                    .iter()
+                    .position(|x| x.timestamp == c.timestamp)
+                    .ok_or(RelicError::CommitNotFound)?
+                    + 1
+            }
+            None => 0,
+        };
+
+        // (text, index of the commit in commits)
+        let mut lines: Option<Vec<(String, Option<usize>)>> = None;
+        for (index, c) in commits[..end].iter().enumerate() {
+            State::blame_apply(&mut lines, &c.change, &path, Some(index));
+        }
+        if commit.is_none() {
+            let change =
+                Change::get_change_all(&self.head_tree(), &self.current, Path::new(&self.path))
+                    .filter_paths(std::slice::from_ref(&path));
+            State::blame_apply(&mut lines, &change, &path, None);
+        }
+
+        let mut lines = lines.ok_or(RelicError::PathNotFound)?;
+        // files ending with a newline have an empty last line
+        if lines.last().is_some_and(|(text, _)| text.is_empty()) {
+            lines.pop();
+        }
+        Ok(lines
+            .into_iter()
+            .map(|(text, origin)| BlameLine {
+                text,
+                commit: origin.map(|i| commits[i].clone()),
+            })
+            .collect())
+    }
+
+    pub fn line_history(
+        &self,
+        path: &Path,
+        start: usize,
+        end: usize,
+    ) -> Result<Vec<LineHistory>, RelicError> {
+        // commits that changed lines start..=end (0-based, as of the checked out commit), most recent first
+        // the range is moved back through each commit, until its lines are created
+        let head = self.head_commit().ok_or(RelicError::PathNotFound)?;
+        let length = self.blame(path, Some(&head))?.len();
+        if start > end || end >= length {
+            return Err(RelicError::LineRangeInvalid);
+        }
+
+        let path = PathBuf::from(".").join(path);
+        let commits = self.branch_commits(&self.info.branch);
+        let position = commits
+            .iter()
+            .position(|c| c.timestamp == head.timestamp)
+            .ok_or(RelicError::CommitNotFound)?;
+
+        let (mut start, mut end) = (start, end);
+        let mut result = vec![];
+        for c in commits[..=position].iter().rev() {
+            let modifications = c
+                .change
+                .blobs
+                .iter()
+                .filter(|m| {
+                    let (p, n) = m.extract_path();
+                    PathBuf::from(p).join(n) == path
+                })
+                .cloned()
+                .collect::<Vec<modifications::Blob>>();
+            if modifications.is_empty() {
+                continue;
+            }
+            let (deleted, created) = Hunk::split_modifications(&modifications);
+
+            // lines of the range that existed before the commit, by their line before it
+            let before = |line: usize| {
+                let mut old = line - created.range(..line).count();
+                for d in deleted.keys() {
+                    if *d <= old {
+                        old += 1;
+                    }
+                }
+                old
+            };
+            let surviving = (start..=end)
+                .filter(|l| !created.contains_key(l))
+                .map(before)
+                .collect::<Vec<usize>>();
+
+            let touched = created.range(start..=end).next().is_some()
+                || match (surviving.first(), surviving.last()) {
+                    (Some(first), Some(last)) => deleted.keys().any(|d| first < d && d < last),
+                    _ => true,
+                };
+            if touched {
+                // only the hunks overlapping the range, on either side of the commit
+                let (old_start, old_end) = (
+                    surviving.first().copied().unwrap_or(usize::MAX),
+                    surviving.last().copied().unwrap_or(0),
+                );
+                let mut offset: i64 = 0;
+                let mut hunks = vec![];
+                for h in Hunk::from_maps(&deleted, &created) {
+                    let new_start = (h.start as i64 + offset) as usize;
+                    if (new_start <= end && start < new_start + h.inserted.len().max(1))
+                        || (h.start <= old_end && old_start < h.end().max(h.start + 1))
+                    {
+                        hunks.push((offset, h.clone()));
+                    }
+                    offset += h.inserted.len() as i64 - h.deleted.len() as i64;
+                }
+                result.push(LineHistory {
+                    commit: c.clone(),
+                    start,
+                    end,
+                    hunks,
+                });
+            }
+
+            match (surviving.first(), surviving.last()) {
+                (Some(first), Some(last)) => (start, end) = (*first, *last),
+                // every line of the range was created here
+                _ => break,
+            }
+        }
+        Ok(result)
+    }
+
+    fn blame_apply(
+        lines: &mut Option<Vec<(String, Option<usize>)>>,
+        change: &Change,
+        path: &Path,
+        origin: Option<usize>,
+    ) {
+        // mirrors Tree::apply_changes and Blob::apply_changes, for a single file
+        let full = |p: &str, n: &str| PathBuf::from(p).join(n);
+        for c_mod in &change.trees {
+            match c_mod {
+                modifications::Tree::DeleteBlob(p, n) if full(p, n) == path => *lines = None,
+                modifications::Tree::DeleteTree(p, n) if path.starts_with(full(p, n)) => {
+                    *lines = None
+                }
+                _ => {}
+            }
+        }
+        for c_mod in &change.trees {
+            if let modifications::Tree::CreateBlob(p, n) = c_mod {
+                if full(p, n) == path {
+                    *lines = Some(vec![("".to_string(), origin)]);
+                }
+            }
+        }
+
+        let Some(lines) = lines else {
+            return;
+        };
+        let mut modifications = change
+            .blobs
+            .iter()
+            .filter(|m| {
+                let (p, n) = m.extract_path();
+                full(&p, &n) == path
+            })
+            .collect::<Vec<&modifications::Blob>>();
+        // deletions from the bottom up, then creations from the top down
+        modifications.sort_by_key(|m| match m {
+            modifications::Blob::Create(_, _, l, _) => (1, *l as i128),
+            modifications::Blob::Delete(_, _, l, _) => (0, -(*l as i128)),
+        });
+        for m in modifications {
+            match m {
+                modifications::Blob::Create(_, _, line, text) => {
+                    lines.insert((*line).min(lines.len()), (text.clone(), origin))
+                }
+                modifications::Blob::Delete(_, _, line, _) => {
+                    if *line < lines.len() {
+                        lines.remove(*line);
+                    }
+                }
+            }
+        }
+    }
+    // #endregion
+}
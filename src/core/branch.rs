@@ -0,0 +1,218 @@
+use std::{fs, path::Path};
+
+use crate::core::{
+    commit::Commit,
+    error::RelicError,
+    modifications::Change,
+    objects::data::Upstream,
+    paths::{
+        RELIC_PATH_BRANCHES, RELIC_PATH_HISTORY, RELIC_PATH_PENDING, RELIC_PATH_STASH,
+        RELIC_PATH_UPSTREAM,
+    },
+    utils, State, Tree,
+};
+
+// everything that belongs to a single branch
+// the current branch lives at these paths, other branches are parked under RELIC_PATH_BRANCHES/{name}/
+pub const BRANCH_CONTENT: [&str; 4] = [
+    RELIC_PATH_UPSTREAM,
+    RELIC_PATH_PENDING,
+    RELIC_PATH_HISTORY,
+    RELIC_PATH_STASH,
+];
+
+impl State {
+    // #region branches
+    pub fn branch_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && !name.starts_with(['.', '-'])
+            && name != "HEAD"
+            // characters used by revisions, eg : main~2, pending:0, main@{u}
+            && !name.contains(|c: char| {
+                c.is_whitespace() || ['/', '\\', '~', '^', ':', '@'].contains(&c)
+            })
+    }
+
+    fn branch_parked_path(name: &str, content: &str) -> String {
+        format!(
+            "{RELIC_PATH_BRANCHES}/{name}/{}",
+            Path::new(content).file_name().unwrap().to_string_lossy()
+        )
+    }
+
+    pub fn branch_path(&self, name: &str, content: &str) -> String {
+        // where a branch's content is currently stored
+        if name == self.info.branch {
+            content.to_string()
+        } else {
+            State::branch_parked_path(name, content)
+        }
+    }
+
+    pub fn branch_list(&self) -> Vec<String> {
+        let mut result = vec![self.info.branch.clone()];
+        if let Ok(d) = fs::read_dir(RELIC_PATH_BRANCHES) {
+            for entry in d.flatten() {
+                if entry.path().is_dir() {
+                    result.push(entry.file_name().to_string_lossy().to_string());
+                }
+            }
+        }
+        result.sort();
+        result
+    }
+
+    pub fn branch_exists(&self, name: &str) -> bool {
+        self.branch_list().iter().any(|b| b == name)
+    }
+
+    pub fn branch_upstream(&self, name: &str) -> Result<Tree, RelicError> {
+        if !self.branch_exists(name) {
+            return Err(RelicError::BranchNotFound);
+        }
+        Ok(Upstream::deserialise(&self.branch_path(name, RELIC_PATH_UPSTREAM))?.tree())
+    }
+
+    pub fn branch_pending(&self, name: &str) -> Vec<Commit> {
+        State::pending_in(&self.branch_path(name, RELIC_PATH_PENDING))
+    }
+
+    pub fn branch_history(&self, name: &str) -> Vec<Commit> {
+        let mut result = State::commits_in(&self.branch_path(name, RELIC_PATH_HISTORY));
+        result.sort_by_key(|c| c.id);
+        result
+    }
+
+    pub fn branch_head(&self, name: &str) -> Option<Commit> {
+        self.branch_pending(name)
+            .pop()
+            .or_else(|| self.branch_history(name).pop())
+    }
+
+    pub fn branch_create(&self, name: &str) -> Result<(), RelicError> {
+        // new branches start off as a copy of the current one, stashes stay where they were made
+        if !State::branch_valid_name(name) {
+            return Err(RelicError::BranchNameInvalid);
+        }
+        if self.branch_exists(name) {
+            return Err(RelicError::BranchExists);
+        }
+
+        for content in BRANCH_CONTENT {
+            if content == RELIC_PATH_STASH || !Path::new(content).exists() {
+                continue;
+            }
+            let destination = self.branch_path(name, content);
+            if utils::copy_recursive(Path::new(content), Path::new(&destination)).is_err() {
+                return Err(RelicError::FileCantOpen);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn branch_switch(&mut self, name: &str) -> Result<(), RelicError> {
+        if name == self.info.branch {
+            return Ok(());
+        }
+        if self.info.detached.is_some() {
+            return Err(RelicError::Detached);
+        }
+
+        let target = self.branch_upstream(name)?;
+        if !self.get_tracked_changes().is_empty() || !self.index_get().is_empty() {
+            return Err(RelicError::UncommittedChanges);
+        }
+
+        let change = Change::get_change_all(&self.upstream, &target, Path::new(&self.path));
+        let collisions = self.untracked_collisions(&change, &self.upstream);
+        if !collisions.is_empty() {
+            return Err(RelicError::UntrackedOverwritten(collisions));
+        }
+
+        // park the current branch, then unpark the target
+        let previous = self.info.branch.clone();
+        fs::create_dir_all(format!("{RELIC_PATH_BRANCHES}/{previous}"))
+            .map_err(|_| RelicError::FileCantOpen)?;
+        let mut moves = vec![];
+        for content in BRANCH_CONTENT {
+            moves.push((
+                content.to_string(),
+                State::branch_parked_path(&previous, content),
+            ));
+            moves.push((
+                State::branch_parked_path(name, content),
+                content.to_string(),
+            ));
+        }
+        // a half parked branch would leave both branches broken, undo what was moved on failure
+        let mut moved = vec![];
+        for (from, to) in moves {
+            if !Path::new(&from).exists() {
+                continue;
+            }
+            if fs::rename(&from, &to).is_err() {
+                for (from, to) in moved.into_iter().rev() {
+                    let _ = fs::rename(to, from);
+                }
+                return Err(RelicError::FileCantOpen);
+            }
+            moved.push((from, to));
+        }
+        let _ = fs::remove_dir_all(format!("{RELIC_PATH_BRANCHES}/{name}"));
+        let _ = fs::create_dir_all(RELIC_PATH_PENDING);
+        let _ = fs::create_dir_all(RELIC_PATH_HISTORY);
+        let _ = fs::create_dir_all(RELIC_PATH_STASH);
+
+        self.info.branch = name.to_string();
+        self.info.save();
+
+        self.apply_to_working_tree(&change, &target);
+        self.upstream = target;
+
+        Ok(())
+    }
+
+    pub fn branch_rename(&mut self, name: &str, new_name: &str) -> Result<(), RelicError> {
+        if !State::branch_valid_name(new_name) {
+            return Err(RelicError::BranchNameInvalid);
+        }
+        if !self.branch_exists(name) {
+            return Err(RelicError::BranchNotFound);
+        }
+        if self.branch_exists(new_name) {
+            return Err(RelicError::BranchExists);
+        }
+
+        if name == self.info.branch {
+            self.info.branch = new_name.to_string();
+            self.info.save();
+        } else if fs::rename(
+            format!("{RELIC_PATH_BRANCHES}/{name}"),
+            format!("{RELIC_PATH_BRANCHES}/{new_name}"),
+        )
+        .is_err()
+        {
+            return Err(RelicError::FileCantOpen);
+        }
+        Ok(())
+    }
+
+    pub fn branch_delete(&self, name: &str, force: bool) -> Result<(), RelicError> {
+        if name == self.info.branch {
+            return Err(RelicError::BranchCurrent);
+        }
+        if !self.branch_exists(name) {
+            return Err(RelicError::BranchNotFound);
+        }
+        // pending commits only exist locally, they would be lost for good
+        if !force && !self.branch_pending(name).is_empty() {
+            return Err(RelicError::BranchHasPending);
+        }
+
+        match fs::remove_dir_all(format!("{RELIC_PATH_BRANCHES}/{name}")) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(RelicError::FileCantOpen),
+        }
+    }
+    // #endregion
+}
@@ -0,0 +1,171 @@
+use std::path::Path;
+
+use crate::core::{commit::Commit, error::RelicError, modifications::Change, utils, State, Tree};
+
+impl State {
+    // #region cherry
+    pub fn commit_get(&self, timestamp: u64) -> Option<Commit> {
+        self.history_get()
+            .into_iter()
+            .chain(self.pending_get())
+            .find(|c| c.timestamp == timestamp)
+    }
+
+    pub fn commit_tree(&self, commit: &Commit) -> Tree {
+        // the tree right after a commit
+        State::unwind(
+            self.upstream.clone(),
+            &self.branch_commits(&self.info.branch),
+            commit,
+        )
+    }
+
+    pub fn branch_commit_tree(&self, name: &str, commit: &Commit) -> Result<Tree, RelicError> {
+        Ok(State::unwind(
+            self.branch_upstream(name)?,
+            &self.branch_commits(name),
+            commit,
+        ))
+    }
+
+    fn unwind(mut tree: Tree, commits: &[Commit], commit: &Commit) -> Tree {
+        // tree already includes every commit, so the ones after commit are unapplied
+        for c in commits
+            .iter()
+            .rev()
+            .take_while(|c| c.timestamp != commit.timestamp)
+        {
+            tree.unapply_changes(&c.change);
+        }
+        tree
+    }
+
+    pub fn head_tree(&self) -> Tree {
+        // the tree the working tree is based on
+        match self.info.detached.and_then(|t| self.commit_get(t)) {
+            Some(c) => self.commit_tree(&c),
+            None => self.upstream.clone(),
+        }
+    }
+
+    pub fn head_commit(&self) -> Option<Commit> {
+        // the commit the working tree is based on
+        match self.info.detached {
+            Some(t) => self.commit_get(t),
+            None => self.branch_head(&self.info.branch),
+        }
+    }
+
+    pub fn cherry(&mut self, commit: &Commit) -> Result<(), RelicError> {
+        // checks out a commit, leaving the working tree detached from the branch tip
+        let target = self.commit_tree(commit);
+        self.move_head(&target)?;
+
+        self.info.detached = Some(commit.timestamp);
+        self.info.save();
+        Ok(())
+    }
+
+    pub fn cherry_tip(&mut self) -> Result<(), RelicError> {
+        let target = self.upstream.clone();
+        self.move_head(&target)?;
+
+        self.info.detached = None;
+        self.info.save();
+        Ok(())
+    }
+
+    pub fn pick(&mut self, name: &str, commit: &Commit) -> Result<Commit, RelicError> {
+        // applies a commit from any branch onto the current one, as a new pending commit
+        let mut base = self.branch_commit_tree(name, commit)?;
+        base.unapply_changes(&commit.change);
+
+        self.commit_onto_upstream(
+            &commit.change,
+            &base,
+            commit.message.clone(),
+            commit.description.clone(),
+        )
+    }
+
+    pub fn revert(&mut self, commit: &Commit) -> Result<Commit, RelicError> {
+        // undoes an earlier commit of the current branch, as a new pending commit
+        let base = self.commit_tree(commit);
+
+        self.commit_onto_upstream(
+            &commit.change.inverse(),
+            &base,
+            format!("Revert \"{}\"", commit.message),
+            "".to_string(),
+        )
+    }
+
+    fn commit_onto_upstream(
+        &mut self,
+        change: &Change,
+        base: &Tree,
+        message: String,
+        description: String,
+    ) -> Result<Commit, RelicError> {
+        // change is made against base, it is rebased over everything that happened between base and upstream
+        if self.info.detached.is_some() {
+            return Err(RelicError::Detached);
+        }
+        if !self.get_tracked_changes().is_empty() || !self.index_get().is_empty() {
+            return Err(RelicError::UncommittedChanges);
+        }
+
+        let over = Change::get_change_all(base, &self.upstream, Path::new(&self.path));
+        let change = change.transform(&over).map_err(RelicError::Conflicts)?;
+        change
+            .verify(&self.upstream)
+            .map_err(RelicError::Conflicts)?;
+        if change.is_empty() {
+            return Err(RelicError::NoChanges);
+        }
+
+        let collisions = self.untracked_collisions(&change, &self.upstream);
+        if !collisions.is_empty() {
+            return Err(RelicError::UntrackedOverwritten(collisions));
+        }
+
+        let commit = Commit {
+            id: None,
+            message,
+            description,
+            change,
+            timestamp: utils::get_time(),
+            author: "no_one".to_string(),
+        };
+        self.pending_add(commit.clone());
+
+        self.upstream_apply(&commit.change);
+        let upstream = self.upstream.clone();
+        self.apply_to_working_tree(&commit.change, &upstream);
+        self.track_add(&State::created_paths(&commit.change));
+
+        Ok(commit)
+    }
+
+    fn move_head(&mut self, target: &Tree) -> Result<(), RelicError> {
+        let head = self.head_tree();
+        let tracked_content = self.get_tracked_content();
+        if !Change::get_change_all(&head, &self.current, Path::new(&self.path))
+            .filter_changes(&tracked_content)
+            .is_empty()
+            || !self.index_get().is_empty()
+        {
+            return Err(RelicError::UncommittedChanges);
+        }
+
+        let change = Change::get_change_all(&head, target, Path::new(&self.path));
+        let collisions = self.untracked_collisions(&change, &head);
+        if !collisions.is_empty() {
+            return Err(RelicError::UntrackedOverwritten(collisions));
+        }
+
+        self.apply_to_working_tree(&change, target);
+        Ok(())
+    }
+    // #endregion
+}
@@ -1,4 +1,10 @@
-use crate::core::{modifications::Change, utils};
+use std::path::{Path, PathBuf};
+
+use crate::core::{
+    error::{ParseError, RelicError},
+    modifications::Change,
+    utils, State,
+};
 
 const PENDING_TAG: &str = "LOCAL";
 
@@ -32,11 +38,15 @@ impl Commit {
         )
     }
 
+    pub fn id_string(&self) -> String {
+        self.id
+            .map_or(PENDING_TAG.to_string(), |i| format!("{:06x}", i))
+    }
+
     pub fn serialise(&self) -> String {
         format!(
             "= {} {} {:?} {:?} {}\n{}",
-            self.id
-                .map_or(PENDING_TAG.to_string(), |i| format!("{:06x}", i).clone()),
+            self.id_string(),
             self.timestamp,
             urlencoding::encode(&self.message).to_string(),
             urlencoding::encode(&self.description).to_string(),
@@ -45,34 +55,117 @@ impl Commit {
         )
     }
 
-    pub fn deserialise(s: String) -> Option<Commit> {
+    pub fn deserialise(s: String) -> Result<Commit, RelicError> {
         // = LOCAL 1747682692319414000 "initial%20commit" "" no_one
 
-        let lines = s.split("\n").collect::<Vec<&str>>();
-        if lines.len() < 2 {
-            // return None;
-        }
+        let invalid = |e: ParseError| RelicError::Parse(0, e);
 
-        let metadata = lines[0].split(" ").collect::<Vec<&str>>();
-        if metadata.len() != 6 {
-            // return None;
-        }
+        let Some((metadata, change)) = s.split_once("\n") else {
+            return Err(invalid(ParseError::MissingChange));
+        };
+
+        let metadata = metadata.split(" ").collect::<Vec<&str>>();
+        let ["=", status, time, message, description, author] = *metadata.as_slice() else {
+            return Err(invalid(ParseError::InvalidMetadata));
+        };
+
+        // quoted and urlencoded
+        let decode = |s: &str| {
+            s.strip_prefix('"')
+                .and_then(|s| s.strip_suffix('"'))
+                .and_then(|s| urlencoding::decode(s).ok())
+                .map(|s| s.to_string())
+                .ok_or(invalid(ParseError::InvalidEncoding))
+        };
+
+        let id = if status == PENDING_TAG {
+            None
+        } else {
+            match u32::from_str_radix(status, 16) {
+                Ok(i) => Some(i),
+                Err(_) => return Err(invalid(ParseError::InvalidMetadata)),
+            }
+        };
 
-        let [_, status, time, message, description, author] = *metadata.as_slice() else {
-            return None;
+        let Ok(timestamp) = time.parse::<u64>() else {
+            return Err(invalid(ParseError::InvalidTimestamp));
         };
 
-        Some(Commit {
-            id: status.parse::<u32>().map_or(None, |t| Some(t)),
-            message: urlencoding::decode(&message[1..message.len() - 1].to_string())
-                .unwrap()
-                .to_string(),
-            description: urlencoding::decode(&description[1..description.len() - 1].to_string())
-                .unwrap()
-                .to_string(),
-            change: Change::deserialise_changes(lines[1..].join("\n")).unwrap_or(Change::empty()),
-            timestamp: time.parse::<u64>().unwrap_or(0),
+        Ok(Commit {
+            id,
+            message: decode(message)?,
+            description: decode(description)?,
+            // offset error lines past the metadata line
+            change: Change::deserialise_changes(change.to_string()).map_err(|e| match e {
+                RelicError::Parse(l, e) => RelicError::Parse(l + 1, e),
+                e => e,
+            })?,
+            timestamp,
             author: author.to_string(),
         })
     }
 }
+
+impl State {
+    // #region commit
+    pub fn commit_create(
+        &mut self,
+        message: String,
+        description: String,
+        paths: Option<&[PathBuf]>,
+        allow_empty: bool,
+    ) -> Result<Commit, RelicError> {
+        // commits the staged changes
+        // with paths, the tracked content at those paths is committed instead, the rest stays staged
+        if self.info.detached.is_some() {
+            return Err(RelicError::Detached);
+        }
+
+        let mut staged = self.index_tree();
+        let change = match paths {
+            Some(paths) => {
+                let tracked_content = self.get_tracked_content();
+                let full = Change::get_change_all(&staged, &self.current, Path::new(&self.path))
+                    .filter_by(|p| !self.is_ignored(p))
+                    .filter_paths(paths);
+                staged.apply_changes(
+                    &full
+                        .filter_by(|p| {
+                            let s = p.to_string_lossy().to_string();
+                            tracked_content.files.contains(&s)
+                                || tracked_content.directories.contains(&s)
+                                || staged.get(p).is_some()
+                        })
+                        .with_parents(&full),
+                );
+                Change::get_change_all(&self.upstream, &staged, Path::new(&self.path))
+                    .filter_paths(paths)
+            }
+            None => self.index_get(),
+        };
+        if change.is_empty() && !allow_empty {
+            return Err(RelicError::NoChanges);
+        }
+
+        let commit = Commit {
+            id: None,
+            message,
+            description,
+            change,
+            timestamp: utils::get_time(),
+            author: "no_one".to_string(),
+        };
+        self.pending_add(commit.clone());
+        self.upstream_apply(&commit.change);
+
+        // whatever was staged but not committed stays staged
+        self.index_save(&Change::get_change_all(
+            &self.upstream,
+            &staged,
+            Path::new(&self.path),
+        ))?;
+
+        Ok(commit)
+    }
+    // #endregion
+}
@@ -1,9 +1,75 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::core::modifications::Conflict;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum RelicError {
     FileCantOpen,
     IgnoredFile,
     ConfigurationIncorrect,
     RelicInfo(Box<RelicError>),
+    Parse(
+        usize, // line
+        ParseError,
+    ),
+
+    // branches
+    BranchNotFound,
+    BranchExists,
+    BranchNameInvalid,
+    BranchCurrent,
+    BranchHasPending,
+
+    // working tree
+    UncommittedChanges,
+    UntrackedOverwritten(Vec<String>),
+    PathNotFound,
+    LineRangeInvalid,
+    NoChanges,
+    Conflicts(Vec<Conflict>),
+
+    // stashes
+    StashNotFound,
+
+    // tags
+    TagNotFound,
+    TagExists,
+    TagNameInvalid,
+
+    // commits
+    CommitNotFound,
+    RevisionInvalid,
+    RevisionAmbiguous,
+
+    // bisect
+    BisectStarted,
+    BisectNotStarted,
+    BisectOrder,
+    Detached,
+
+    // remotes
+    RemoteNotSet,
+    RemoteNotFound,
+    RemoteAhead,
+    RemoteDiverged,
+    RemoteBusy,
+    DirectoryNotEmpty,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ParseError {
+    // commit
+    InvalidMetadata,
+    InvalidTimestamp,
+    MissingChange,
+
+    // change
+    InvalidTree,
+    InvalidBlobHeader,
+    MissingBlobHeader,
+    InvalidChange,
+    InvalidLineIndex,
+
+    // urlencoding or text escaping
+    InvalidEncoding,
 }
@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+use crate::core::{State, Tree};
+
+// a line containing the searched text, line is 1-based
+#[derive(Debug, Clone)]
+pub struct GrepMatch {
+    pub path: PathBuf,
+    pub line: usize,
+    pub text: String,
+}
+
+impl State {
+    // #region grep
+    pub fn grep(
+        &mut self,
+        pattern: &str,
+        tree: Option<&Tree>,
+        paths: &[PathBuf],
+        ignore_case: bool,
+    ) -> Vec<GrepMatch> {
+        // searches the files of a tree, or the tracked files of the working tree without one
+        let tracked = match tree {
+            Some(_) => None,
+            None => Some(self.get_tracked_content()),
+        };
+        let tree = tree.unwrap_or(&self.current);
+        let paths = paths
+            .iter()
+            .map(|p| PathBuf::from(".").join(p))
+            .collect::<Vec<PathBuf>>();
+        let pattern = if ignore_case {
+            pattern.to_lowercase()
+        } else {
+            pattern.to_string()
+        };
+
+        let mut result = vec![];
+        for (path, blob) in tree.files() {
+            if tracked
+                .as_ref()
+                .is_some_and(|t| !t.files.contains(&path.to_string_lossy().to_string()))
+                || !(paths.is_empty() || paths.iter().any(|p| path.starts_with(p)))
+            {
+                continue;
+            }
+
+            for (index, text) in blob.content.split("\n").enumerate() {
+                let found = if ignore_case {
+                    text.to_lowercase().contains(&pattern)
+                } else {
+                    text.contains(&pattern)
+                };
+                if found {
+                    result.push(GrepMatch {
+                        path: path.clone(),
+                        line: index + 1,
+                        text: text.to_string(),
+                    });
+                }
+            }
+        }
+        result
+    }
+    // #endregion
+}
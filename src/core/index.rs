@@ -0,0 +1,224 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::core::{
+    error::RelicError,
+    modifications::{self, Change, Hunk},
+    paths::RELIC_PATH_INDEX,
+    State, Tree,
+};
+
+// a part of a change that can be staged on its own
+pub enum Selection<'a> {
+    Hunk(&'a Hunk),
+    // a file or directory created or deleted without any lines, eg : an empty file
+    Tree(&'a modifications::Tree),
+}
+
+impl State {
+    // #region index
+    // the index holds the staged changes, made against upstream
+    // files are staged as a snapshot, later edits are left out until they are staged again
+    pub fn index_get(&self) -> Change {
+        match fs::read_to_string(RELIC_PATH_INDEX) {
+            Ok(s) if !s.is_empty() => match Change::deserialise_changes(s) {
+                Ok(c) => c,
+                Err(e) => {
+                    println!("Ignoring invalid index : {e:?}");
+                    Change::empty()
+                }
+            },
+            _ => Change::empty(),
+        }
+    }
+
+    pub fn index_save(&self, change: &Change) -> Result<(), RelicError> {
+        let content = if change.is_empty() {
+            "".to_string()
+        } else {
+            change.serialise_changes()
+        };
+        fs::write(RELIC_PATH_INDEX, content).map_err(|_| RelicError::FileCantOpen)
+    }
+
+    pub fn index_clear(&self) {
+        let _ = self.index_save(&Change::empty());
+    }
+
+    pub fn index_tree(&self) -> Tree {
+        // upstream, with the staged changes applied
+        let mut tree = self.upstream.clone();
+        tree.apply_changes(&self.index_get());
+        tree
+    }
+
+    pub fn index_add(&mut self, paths: &[PathBuf]) -> Result<Change, RelicError> {
+        // snapshots the working tree at the given paths into the index
+        let mut staged = self.index_tree();
+        let change = Change::get_change_all(&staged, &self.current, Path::new(&self.path))
+            .filter_by(|p| !self.is_ignored(p))
+            .filter_paths(paths);
+        self.index_update(&mut staged, &change)?;
+        Ok(change)
+    }
+
+    pub fn index_add_hunks<F>(
+        &mut self,
+        paths: &[PathBuf],
+        mut accept: F,
+    ) -> Result<Change, RelicError>
+    where
+        F: FnMut(&Path, &Selection) -> bool,
+    {
+        // stages only the accepted hunks of the given files, in path order
+        // a file is only created once any of its hunks are, and deleted once all of them are
+        // modifications without any lines (empty files, directories) are offered on their own
+        let mut staged = self.index_tree();
+        let change = Change::get_change_all(&staged, &self.current, Path::new(&self.path))
+            .filter_by(|p| !self.is_ignored(p))
+            .filter_paths(paths);
+        let full = |c_mod: &modifications::Tree| {
+            let (p, n) = c_mod.extract_data();
+            PathBuf::from(p).join(n)
+        };
+
+        let files = change
+            .as_map()
+            .1
+            .into_iter()
+            .flat_map(|(p, names)| names.into_iter().map(move |(n, m)| (p.clone(), n, m)))
+            .collect::<Vec<(String, String, Vec<modifications::Blob>)>>();
+        let lined = files
+            .iter()
+            .map(|(p, n, _)| PathBuf::from(p).join(n))
+            .collect::<Vec<PathBuf>>();
+        let bare = change
+            .trees
+            .iter()
+            .filter(|c_mod| {
+                let path = full(c_mod);
+                match c_mod {
+                    modifications::Tree::CreateBlob(_, _)
+                    | modifications::Tree::DeleteBlob(_, _) => !lined.contains(&path),
+                    // directories holding other modifications come along with them
+                    _ => {
+                        !lined.iter().any(|l| l.starts_with(&path))
+                            && !change
+                                .trees
+                                .iter()
+                                .any(|o| o != *c_mod && full(o).starts_with(&path))
+                    }
+                }
+            })
+            .collect::<Vec<&modifications::Tree>>();
+
+        enum Offered<'a> {
+            Lines(String, String, Vec<modifications::Blob>),
+            Bare(&'a modifications::Tree),
+        }
+        let mut selections = files
+            .into_iter()
+            .map(|(p, n, m)| (PathBuf::from(&p).join(&n), Offered::Lines(p, n, m)))
+            .chain(
+                bare.into_iter()
+                    .map(|c_mod| (full(c_mod), Offered::Bare(c_mod))),
+            )
+            .collect::<Vec<(PathBuf, Offered)>>();
+        selections.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut result = Change::empty();
+        for (path, selection) in selections {
+            let (p, n, m) = match selection {
+                Offered::Lines(p, n, m) => (p, n, m),
+                Offered::Bare(c_mod) => {
+                    if accept(&path, &Selection::Tree(c_mod)) {
+                        result.trees.push(c_mod.clone());
+                    }
+                    continue;
+                }
+            };
+
+            let hunks = Hunk::from_modifications(&m);
+            let accepted = hunks
+                .iter()
+                .filter(|h| accept(&path, &Selection::Hunk(h)))
+                .cloned()
+                .collect::<Vec<Hunk>>();
+            if accepted.is_empty() {
+                continue;
+            }
+
+            let whole = accepted.len() == hunks.len();
+            for c_mod in &change.trees {
+                match c_mod {
+                    modifications::Tree::CreateBlob(t_p, t_n) if *t_p == p && *t_n == n => {
+                        result.trees.push(c_mod.clone())
+                    }
+                    modifications::Tree::DeleteBlob(t_p, t_n)
+                        if whole && *t_p == p && *t_n == n =>
+                    {
+                        result.trees.push(c_mod.clone())
+                    }
+                    _ => {}
+                }
+            }
+            result
+                .blobs
+                .append(&mut Hunk::into_modifications(&accepted, &p, &n));
+        }
+
+        // a deleted directory goes once everything in it is deleted, deepest first
+        let mut deleted = change
+            .trees
+            .iter()
+            .filter(|c_mod| matches!(c_mod, modifications::Tree::DeleteTree(_, _)))
+            .filter(|c_mod| !result.trees.contains(c_mod))
+            .collect::<Vec<&modifications::Tree>>();
+        deleted.sort_by_key(|c_mod| std::cmp::Reverse(full(c_mod).components().count()));
+        for c_mod in deleted {
+            let path = full(c_mod);
+            let inside = change
+                .trees
+                .iter()
+                .filter(|o| {
+                    matches!(
+                        o,
+                        modifications::Tree::DeleteTree(_, _)
+                            | modifications::Tree::DeleteBlob(_, _)
+                    ) && *o != c_mod
+                        && full(o).starts_with(&path)
+                })
+                .collect::<Vec<&modifications::Tree>>();
+            if !inside.is_empty() && inside.iter().all(|o| result.trees.contains(o)) {
+                result.trees.push(c_mod.clone());
+            }
+        }
+
+        // directories leading up to the created blobs
+        let result = result.with_parents(&change);
+
+        self.index_update(&mut staged, &result)?;
+        Ok(result)
+    }
+
+    pub fn index_remove(&mut self, paths: &[PathBuf]) -> Result<Change, RelicError> {
+        // resets the index at the given paths back to upstream, the working tree is left alone
+        let mut staged = self.index_tree();
+        let change = Change::get_change_all(&staged, &self.upstream, Path::new(&self.path))
+            .filter_paths(paths);
+        self.index_update(&mut staged, &change)?;
+        Ok(change)
+    }
+
+    fn index_update(&self, staged: &mut Tree, change: &Change) -> Result<(), RelicError> {
+        staged.apply_changes(change);
+        self.index_save(&Change::get_change_all(
+            &self.upstream,
+            staged,
+            Path::new(&self.path),
+        ))
+    }
+    // #endregion
+}
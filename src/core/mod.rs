@@ -9,7 +9,19 @@ pub mod relic;
 pub mod relic_info;
 pub mod state;
 
+pub mod bisect;
+pub mod blame;
+pub mod branch;
+pub mod cherry;
 pub mod commit;
+pub mod grep;
+pub mod index;
+pub mod pending;
+pub mod remote;
+pub mod revision;
+pub mod stash;
+pub mod status;
+pub mod tag;
 
 pub use objects::{content_set, modifications, Blob, Content, ContentMutRef, Tree};
 pub use relic_info::RelicInfo;
@@ -30,15 +30,6 @@ impl Blob {
 
     pub fn apply_changes(&mut self, modifications: &Vec<modifications::Blob>) {
         // TODO : investigate whether an additional newline is added to eof
-        // BUG : when the file has only one line, diffs start to break
-        //
-        // content : ""
-        // Create(,, 0, "something")
-        // result : "something\nsomething"
-        //
-        // content : "something\nsomething"
-        // Delete(,, 0)
-        // result : ""
 
         // CHANGES ARE BEING APPLIED TO THE WRONG FILE
         // APPLY CHANGES TO UPSTREAM, NOT CURRENT
@@ -54,10 +45,12 @@ impl Blob {
             .map(|x| x.to_string())
             .collect::<Vec<String>>();
 
+        // all deletions must happen before any creation
+        // (a deletion and a creation on the same line would otherwise be ordered arbitrarily)
         let mut modifications = modifications.clone();
         modifications.sort_by_key(|m| match m {
-            modifications::Blob::Create(_, _, l, _) => *l as i128,
-            modifications::Blob::Delete(_, _, l, _) => -(*l as i128),
+            modifications::Blob::Create(_, _, l, _) => (1, *l as i128),
+            modifications::Blob::Delete(_, _, l, _) => (0, -(*l as i128)),
         });
 
         for m in &modifications {
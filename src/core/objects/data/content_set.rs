@@ -102,16 +102,18 @@ impl TrackingSet for ContentSet {
                 let mut tracked_unlock = tracked_mutex.lock().unwrap();
 
                 match current {
-                    ContentMutRef::Tree(t) => {
+                    ContentMutRef::Tree(_) => {
                         // if parent in set
                         // add to content set
-                        if tracked_unlock
-                            .directories
-                            .contains(&t.path.parent().unwrap().to_string_lossy().to_string())
-                        {
+                        // (path rather than Tree::path, upstream trees dont have it set)
+                        if path.parent().is_some_and(|p| {
+                            tracked_unlock
+                                .directories
+                                .contains(&p.to_string_lossy().to_string())
+                        }) {
                             tracked_unlock
                                 .directories
-                                .insert(t.path.to_string_lossy().to_string());
+                                .insert(path.to_string_lossy().to_string());
                         }
                     }
                     ContentMutRef::Blob(b) => {
@@ -1,6 +1,6 @@
 use std::{
     collections::{HashMap, HashSet},
-    path::PathBuf,
+    path::{Component, Path, PathBuf},
     sync::{Arc, Mutex},
 };
 
@@ -51,6 +51,47 @@ impl Tree {
     //     serde_json::to_string_pretty(&self).unwrap()
     // }
 
+    pub fn get(&self, path: &Path) -> Option<&Content> {
+        // path relative to this tree, eg : "./lorem/ipsum/dolor"
+        let names = path
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(n) => Some(n.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+
+        let mut tree = self;
+        for (index, name) in names.iter().enumerate() {
+            let content = tree.content.iter().find(|c| c.get_name() == *name)?;
+            if index == names.len() - 1 {
+                return Some(content);
+            }
+            match content {
+                Content::Tree(t) => tree = t,
+                Content::Blob(_) => return None,
+            }
+        }
+        None
+    }
+
+    pub fn files(&self) -> Vec<(PathBuf, &Blob)> {
+        // every blob below this tree, with its path relative to it, eg : "./lorem/ipsum"
+        fn inner<'a>(path: PathBuf, tree: &'a Tree, result: &mut Vec<(PathBuf, &'a Blob)>) {
+            for c in &tree.content {
+                match c {
+                    Content::Tree(t) => inner(path.join(&t.name), t, result),
+                    Content::Blob(b) => result.push((path.join(&b.name), b)),
+                }
+            }
+        }
+
+        let mut result = vec![];
+        inner(PathBuf::from("."), self, &mut result);
+        result.sort_by(|a, b| a.0.cmp(&b.0));
+        result
+    }
+
     pub fn apply_changes(&mut self, changes: &Change) {
         let (c_mod_map, mod_map) = changes.as_map();
         let c_mod_map = Arc::new(Mutex::new(c_mod_map));
@@ -78,15 +119,16 @@ impl Tree {
                             &mut c_mod_map_lock,
                         ));
 
+                        // (name, is_blob), a blob may be replaced by a tree of the same name
                         let mut deleted_containers = HashSet::new();
                         // deals with subtractions
                         for c_mod in &c_clone {
                             match c_mod {
                                 modifications::Tree::DeleteTree(_, n) => {
-                                    deleted_containers.insert(n);
+                                    deleted_containers.insert((n, false));
                                 }
                                 modifications::Tree::DeleteBlob(_, n) => {
-                                    deleted_containers.insert(n);
+                                    deleted_containers.insert((n, true));
                                 }
                                 _ => {}
                             }
@@ -96,9 +138,9 @@ impl Tree {
                             .content
                             .iter()
                             .filter(|x| {
-                                !deleted_containers.contains(match x {
-                                    Content::Blob(b) => &b.name,
-                                    Content::Tree(t) => &t.name,
+                                !deleted_containers.contains(&match x {
+                                    Content::Blob(b) => (&b.name, true),
+                                    Content::Tree(t) => (&t.name, false),
                                 })
                             })
                             .map(|x| x.clone())
@@ -162,7 +204,6 @@ impl Tree {
     }
 
     pub fn unapply_changes(&mut self, changes: &Change) {
-        // TODO : test if 100% reliable
         let changes = changes.inverse();
         self.apply_changes(&changes);
         // TODO : update upstream?
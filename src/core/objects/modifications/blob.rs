@@ -35,7 +35,10 @@ impl Blob {
             },
             match self {
                 Blob::Create(_, _, line, content) | Blob::Delete(_, _, line, content) => {
-                    format!("{line} {content:?}")
+                    // json string escaping, readable by `Change::deserialise_changes`
+                    // control characters differ from the debug formatting used before (\u001b, not \u{1b})
+                    // older changes are still read, see `utils::from_debug_string`
+                    format!("{line} {}", serde_json::to_string(content).unwrap())
                 }
             }
         )
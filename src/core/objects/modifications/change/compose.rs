@@ -0,0 +1,127 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::core::modifications::{self, Hunk};
+
+use super::Change;
+
+impl Change {
+    pub fn compose(a: &Change, b: &Change) -> Change {
+        // returns a single change equivalent to applying a, then b
+        //
+        // a : x -> y
+        // b : y -> z
+        // result : x -> z
+
+        // #region containers
+        // opposing operations on the same entry cancel out
+        //      created by a, deleted by b -> never existed
+        //      deleted by a, created by b -> still exists, only the content changed
+        let mut trees = a.trees.clone();
+        let mut transient = HashSet::new();
+        for t in &b.trees {
+            let opposite = t.inverse();
+            if let Some(index) = trees.iter().position(|x| *x == opposite) {
+                trees.remove(index);
+                if let modifications::Tree::DeleteBlob(p, n) = t {
+                    transient.insert((p.clone(), n.clone()));
+                }
+            } else if !trees.contains(t) {
+                trees.push(t.clone());
+            }
+        }
+        // #endregion
+
+        // #region blobs
+        let (_, a_map) = a.as_map();
+        let (_, b_map) = b.as_map();
+
+        let mut files = BTreeSet::new();
+        for map in [&a_map, &b_map] {
+            for (path, names) in map {
+                for name in names.keys() {
+                    files.insert((path.clone(), name.clone()));
+                }
+            }
+        }
+
+        let mut blobs = vec![];
+        for (path, name) in files {
+            if transient.contains(&(path.clone(), name.clone())) {
+                // file does not outlive the composition, its content is irrelevant
+                continue;
+            }
+
+            let a_modifications = a_map
+                .get(&path)
+                .and_then(|m| m.get(&name))
+                .cloned()
+                .unwrap_or_default();
+            let b_modifications = b_map
+                .get(&path)
+                .and_then(|m| m.get(&name))
+                .cloned()
+                .unwrap_or_default();
+
+            let (a_deleted, a_created) = Hunk::split_modifications(&a_modifications);
+            let (b_deleted, b_created) = Hunk::split_modifications(&b_modifications);
+
+            let mut deleted = a_deleted.clone();
+            let mut created = b_created.clone();
+
+            for (y, text) in &b_deleted {
+                // a line created by a and deleted by b never existed
+                if !a_created.contains_key(y) {
+                    deleted.insert(Change::renumber(*y, &a_created, &a_deleted), text.clone());
+                }
+            }
+
+            for (y, text) in &a_created {
+                if !b_deleted.contains_key(y) {
+                    created.insert(Change::renumber(*y, &b_deleted, &b_created), text.clone());
+                }
+            }
+
+            let hunks = Hunk::from_maps(&deleted, &created)
+                .into_iter()
+                .map(|mut h| {
+                    h.trim();
+                    h
+                })
+                .filter(|h| !h.is_empty())
+                .collect::<Vec<Hunk>>();
+
+            blobs.append(&mut Hunk::into_modifications(&hunks, &path, &name));
+        }
+        // #endregion
+
+        Change { trees, blobs }
+    }
+
+    pub fn compose_all(changes: &[Change]) -> Change {
+        changes
+            .iter()
+            .fold(Change::empty(), |result, c| Change::compose(&result, c))
+    }
+
+    pub(super) fn renumber(
+        line: usize,
+        removed: &BTreeMap<usize, String>,
+        added: &BTreeMap<usize, String>,
+    ) -> usize {
+        // maps a line that survives a change from one side of it to the other
+        //      removed : lines on the side `line` belongs to that have no counterpart
+        //      added : lines on the other side that have no counterpart
+        let rank = line - removed.range(..line).count();
+
+        // the rank-th line (0 indexed) that is not in `added`
+        let mut result = rank;
+        for l in added.keys() {
+            if *l <= result {
+                result += 1;
+            } else {
+                break;
+            }
+        }
+        result
+    }
+}
@@ -20,10 +20,10 @@ impl Change {
         // basically a shortest distance problem, with downwards, rightwards and diagonal directions as movement choices
         // (note that diagonal movements do not contribute towards the distance)
 
-        // similar does not handle newlines at eof well at all
-        // this is the workaround for it
-        let upstream = format!("{}\n", upstream_blob.content.clone());
-        let current = format!("{}\n", current_blob.content.clone());
+        // diff line by line, splitting exactly the way Blob::apply_changes joins
+        // (similar's own line splitting also breaks on lone '\r' and mishandles newlines at eof)
+        let upstream = upstream_blob.content.split("\n").collect::<Vec<&str>>();
+        let current = current_blob.content.split("\n").collect::<Vec<&str>>();
 
         // TODO : compare hashes instead of blobs
         if upstream == current {
@@ -31,24 +31,24 @@ impl Change {
         }
 
         let mut result = vec![];
-        let diff = TextDiff::from_lines(&upstream, &current);
+        let diff = TextDiff::from_slices(&upstream, &current);
 
-        for change in diff.iter_all_changes().filter_map(|c| match c.tag() {
-            ChangeTag::Equal => None,
-            _ => Some(c),
-        }) {
+        for change in diff
+            .iter_all_changes()
+            .filter(|c| c.tag() != ChangeTag::Equal)
+        {
             result.push(match change.tag() {
                 ChangeTag::Delete => modifications::Blob::Delete(
                     path.clone(),
                     current_blob.name.clone(),
                     change.old_index().unwrap(),
-                    change.to_string().strip_suffix("\n").unwrap().to_string(),
+                    change.value().to_string(),
                 ),
                 ChangeTag::Insert => modifications::Blob::Create(
                     path.clone(),
                     current_blob.name.clone(),
                     change.new_index().unwrap(),
-                    change.to_string().strip_suffix("\n").unwrap().to_string(),
+                    change.value().to_string(),
                 ),
                 _ => panic!("Unmatched change type: {}", change),
             })
@@ -114,7 +114,20 @@ impl Change {
             if is_blob {
                 container_modifications.push(modifications::Tree::DeleteBlob(
                     path.to_string_lossy().to_string(),
-                    name,
+                    name.clone(),
+                ));
+                // log the removed content as well, otherwise the deletion cannot be inversed
+                let deleted_blob = match upstream_map.get(&(name, true)).unwrap() {
+                    Content::Blob(b) => b,
+                    _ => panic!(),
+                };
+                modifications.append(&mut Change::get_change(
+                    path.to_string_lossy().to_string(),
+                    deleted_blob,
+                    &Blob {
+                        name: deleted_blob.name.clone(),
+                        content: "".to_string(),
+                    },
                 ));
             } else {
                 container_modifications.push(modifications::Tree::DeleteTree(
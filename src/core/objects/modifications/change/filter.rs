@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::core::{content_set::ContentSet, modifications};
 
@@ -38,4 +38,61 @@ impl Change {
                 .collect(),
         }
     }
+
+    pub fn filter_paths(&self, paths: &[PathBuf]) -> Change {
+        // keeps modifications at or below any of the given paths, eg : "./lorem" keeps "./lorem/ipsum"
+        let paths = paths
+            .iter()
+            .map(|p| PathBuf::from(".").join(p))
+            .collect::<Vec<PathBuf>>();
+        self.filter_by(|path| paths.iter().any(|p| path.starts_with(p)))
+            .with_parents(self)
+    }
+
+    pub fn with_parents(&self, source: &Change) -> Change {
+        // adds the directories from source that have to be created for the content in self
+        let mut result = self.clone();
+        let paths = self
+            .trees
+            .iter()
+            .map(|c_mod| c_mod.extract_data())
+            .chain(self.blobs.iter().map(|m| m.extract_path()))
+            .map(|(p, n)| PathBuf::from(p).join(n))
+            .collect::<Vec<PathBuf>>();
+        for c_mod in &source.trees {
+            if let modifications::Tree::CreateTree(p, n) = c_mod {
+                let full = PathBuf::from(p).join(n);
+                if paths.iter().any(|p| p.starts_with(&full)) && !result.trees.contains(c_mod) {
+                    result.trees.push(c_mod.clone());
+                }
+            }
+        }
+        result
+    }
+
+    pub fn filter_by<F>(&self, f: F) -> Change
+    where
+        F: Fn(&Path) -> bool,
+    {
+        Change {
+            trees: self
+                .trees
+                .iter()
+                .filter(|c_mod| {
+                    let (p, n) = c_mod.extract_data();
+                    f(&PathBuf::from(p).join(n))
+                })
+                .cloned()
+                .collect(),
+            blobs: self
+                .blobs
+                .iter()
+                .filter(|m| {
+                    let (p, n) = m.extract_path();
+                    f(&PathBuf::from(p).join(n))
+                })
+                .cloned()
+                .collect(),
+        }
+    }
 }
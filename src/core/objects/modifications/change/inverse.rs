@@ -4,8 +4,6 @@ use super::Change;
 
 impl Change {
     pub fn inverse(&self) -> Change {
-        // TODO: test
-
         // returns inverse of the change
         // all additions are deletions and vice versa
 
@@ -17,20 +15,7 @@ impl Change {
             trees: self
                 .trees
                 .iter()
-                .map(|c| match c {
-                    modifications::Tree::CreateBlob(p, n) => {
-                        modifications::Tree::DeleteBlob(p.to_string(), n.to_string())
-                    }
-                    modifications::Tree::CreateTree(p, n) => {
-                        modifications::Tree::DeleteTree(p.to_string(), n.to_string())
-                    }
-                    modifications::Tree::DeleteBlob(p, n) => {
-                        modifications::Tree::CreateBlob(p.to_string(), n.to_string())
-                    }
-                    modifications::Tree::DeleteTree(p, n) => {
-                        modifications::Tree::CreateTree(p.to_string(), n.to_string())
-                    }
-                })
+                .map(|c| c.inverse())
                 .collect::<Vec<modifications::Tree>>(),
             blobs: self
                 .blobs
@@ -1,10 +1,16 @@
+mod compose;
 mod constructor;
 mod filter;
 mod inverse;
+mod rename;
 mod serialisation;
+mod transform;
+
+pub use transform::Conflict;
 
 use std::{
     collections::{HashMap, HashSet},
+    path::PathBuf,
     thread::current,
 };
 
@@ -12,7 +18,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::core::{content_set::ContentSet, modifications, utils, State, Tree};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Change {
     pub trees: Vec<modifications::Tree>,
     pub blobs: Vec<modifications::Blob>,
@@ -25,6 +31,10 @@ impl Change {
         }
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.trees.is_empty() && self.blobs.is_empty()
+    }
+
     pub fn get_affected_blobs(&self) -> Vec<String> {
         let mut blobs = vec![];
         for (_, parent) in self.as_map().1 {
@@ -33,29 +43,45 @@ impl Change {
         blobs
     }
 
+    pub fn get_stat(&self) -> Vec<(String, usize, usize)> {
+        // (path, additions, deletions) for every modified blob, sorted by path
+        let mut result = vec![];
+        for (path, parent) in self.as_map().1 {
+            for (name, modifications) in parent {
+                let additions = modifications
+                    .iter()
+                    .filter(|m| matches!(m, modifications::Blob::Create(_, _, _, _)))
+                    .count();
+                result.push((
+                    PathBuf::from(&path).join(name).to_string_lossy().to_string(),
+                    additions,
+                    modifications.len() - additions,
+                ));
+            }
+        }
+        result.sort();
+        result
+    }
+
+    pub fn changes_occurrences(&self, text: &str) -> bool {
+        // whether any file gains or loses occurrences of text, lines that are only moved around do not count
+        let mut counts: HashMap<(String, String), i64> = HashMap::new();
+        for m in &self.blobs {
+            let (sign, line) = match m {
+                modifications::Blob::Create(_, _, _, l) => (1, l),
+                modifications::Blob::Delete(_, _, _, l) => (-1, l),
+            };
+            *counts.entry(m.extract_path()).or_insert(0) +=
+                sign * line.matches(text).count() as i64;
+        }
+        counts.values().any(|c| *c != 0)
+    }
+
     pub fn as_human_readable(&self, current_upstream: &Tree) -> String {
         // HashMap<String, HashSet<modifications::Tree>>,
         // HashMap<String, HashMap<String, Vec<modifications::Blob>>>,
 
-        let mut changes = self.clone();
-        println!("{:?}", self.trees);
-        changes.trees = changes
-            .trees
-            .clone()
-            .into_iter()
-            .filter(|t| match t {
-                modifications::Tree::DeleteBlob(_, _) | modifications::Tree::DeleteTree(_, _) => {
-                    false
-                }
-                _ => true,
-            })
-            .collect::<Vec<modifications::Tree>>();
-        println!("{:?}", self.trees);
-
-        let (tree_map, blob_map) = self.as_map();
-
-        let mut current_upstream = current_upstream.clone();
-        current_upstream.apply_changes(&changes);
+        let (_, blob_map) = self.as_map();
 
         /*
             {full change}
@@ -108,12 +134,30 @@ impl Change {
         format!(
             "{}\n\n{}\n\n{affected_files} files affected, {} additions, {} deletions",
             self.serialise_changes(),
-            utils::generate_blame_tree(&current_upstream, &tree_map, &blob_map),
+            self.as_blame_tree(current_upstream),
             addition,
             deletion
         )
     }
 
+    pub fn as_blame_tree(&self, parent: &Tree) -> String {
+        // parent is the tree the change was made against
+        // deletions are left out, so that deleted content still shows up in the tree
+        let mut changes = self.clone();
+        changes.trees.retain(|t| {
+            !matches!(
+                t,
+                modifications::Tree::DeleteBlob(_, _) | modifications::Tree::DeleteTree(_, _)
+            )
+        });
+
+        let (tree_map, blob_map) = self.as_map();
+
+        let mut tree = parent.clone();
+        tree.apply_changes(&changes);
+        utils::generate_blame_tree(&tree, &tree_map, &blob_map)
+    }
+
     pub fn as_map(
         &self,
     ) -> (
@@ -0,0 +1,81 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use crate::core::modifications::{self, Change};
+
+impl Change {
+    pub fn get_renames(&self) -> Vec<(PathBuf, PathBuf)> {
+        // (from, to) for every deleted file whose content reappears in a created file
+        // a pair counts as a rename when at least half of the lines are shared
+        let full = |p: &str, n: &str| PathBuf::from(p).join(n);
+        let lines = |deleted: bool, path: &PathBuf| {
+            let mut lines = self
+                .blobs
+                .iter()
+                .filter_map(|m| match m {
+                    modifications::Blob::Delete(p, n, _, t) if deleted && full(p, n) == *path => {
+                        Some(t.clone())
+                    }
+                    modifications::Blob::Create(p, n, _, t) if !deleted && full(p, n) == *path => {
+                        Some(t.clone())
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<String>>();
+            // trailing newlines are not content
+            lines.retain(|l| !l.is_empty());
+            lines
+        };
+
+        let mut deleted = vec![];
+        let mut created = vec![];
+        for c_mod in &self.trees {
+            match c_mod {
+                modifications::Tree::DeleteBlob(p, n) => deleted.push(full(p, n)),
+                modifications::Tree::CreateBlob(p, n) => created.push(full(p, n)),
+                _ => {}
+            }
+        }
+
+        let mut result = vec![];
+        for from in deleted {
+            let old = lines(true, &from);
+            if old.is_empty() {
+                continue;
+            }
+
+            let best = created
+                .iter()
+                .map(|to| {
+                    let new = lines(false, to);
+                    let mut counts = HashMap::new();
+                    for l in &old {
+                        *counts.entry(l).or_insert(0) += 1;
+                    }
+                    let shared = new
+                        .iter()
+                        .filter(|l| match counts.get_mut(l) {
+                            Some(c) if *c > 0 => {
+                                *c -= 1;
+                                true
+                            }
+                            _ => false,
+                        })
+                        .count();
+                    (
+                        shared * 2 >= old.len().max(new.len()) && shared > 0,
+                        shared,
+                        to,
+                    )
+                })
+                .filter(|(similar, _, _)| *similar)
+                .max_by_key(|(_, shared, _)| *shared);
+
+            if let Some((_, _, to)) = best {
+                let to = to.clone();
+                created.retain(|c| *c != to);
+                result.push((from, to));
+            }
+        }
+        result
+    }
+}
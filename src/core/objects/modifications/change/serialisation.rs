@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 
-use crate::core::modifications;
+use crate::core::{
+    error::{ParseError, RelicError},
+    modifications, utils,
+};
 
 use super::Change;
 
@@ -53,7 +56,7 @@ impl Change {
         result.join("\n")
     }
 
-    pub fn deserialise_changes(s: String) -> Option<Change> {
+    pub fn deserialise_changes(s: String) -> Result<Change, RelicError> {
         // + D . src
         // + F .%2Fsrc utils.rs
         // + F .%2Fsrc branch.rs
@@ -62,16 +65,20 @@ impl Change {
         // + 0 "use std::{collections::{HashMap, HashSet}, fs, path::{Path, PathBuf}, sync::{Arc, Mutex}};"
         // + 1 ""
 
-        let lines = s
-            .split("\n")
-            .map(|x| x.to_string())
-            .collect::<Vec<String>>();
+        // line numbers in errors are 0 indexed, relative to the start of the change
+        let decode = |index: usize, s: &str| match urlencoding::decode(s) {
+            Ok(d) => Ok(d.to_string()),
+            Err(_) => Err(RelicError::Parse(index, ParseError::InvalidEncoding)),
+        };
 
         let mut result = Change::empty();
         let mut tree_section = true;
 
         let mut previous_blob = None;
-        for l in lines {
+        for (index, l) in s.split("\n").enumerate() {
+            if l.is_empty() {
+                continue;
+            }
             if tree_section && (l == "=") {
                 tree_section = false;
                 continue;
@@ -80,93 +87,55 @@ impl Change {
 
             if tree_section {
                 let [species, container, parent, name] = *content.as_slice() else {
-                    return None;
+                    return Err(RelicError::Parse(index, ParseError::InvalidTree));
                 };
 
+                let (parent, name) = (decode(index, parent)?, decode(index, name)?);
                 result.trees.push(match (species, container) {
-                    ("+", "D") => modifications::Tree::CreateTree(
-                        urlencoding::decode(parent).unwrap().to_string(),
-                        urlencoding::decode(name).unwrap().to_string(),
-                    ),
-                    ("-", "D") => modifications::Tree::DeleteTree(
-                        urlencoding::decode(parent).unwrap().to_string(),
-                        urlencoding::decode(name).unwrap().to_string(),
-                    ),
-                    ("+", "F") => modifications::Tree::CreateBlob(
-                        urlencoding::decode(parent).unwrap().to_string(),
-                        urlencoding::decode(name).unwrap().to_string(),
-                    ),
-                    ("-", "F") => modifications::Tree::DeleteBlob(
-                        urlencoding::decode(parent).unwrap().to_string(),
-                        urlencoding::decode(name).unwrap().to_string(),
-                    ),
-                    _ => {
-                        println!("invalid tree");
-                        return None;
-                    }
+                    ("+", "D") => modifications::Tree::CreateTree(parent, name),
+                    ("-", "D") => modifications::Tree::DeleteTree(parent, name),
+                    ("+", "F") => modifications::Tree::CreateBlob(parent, name),
+                    ("-", "F") => modifications::Tree::DeleteBlob(parent, name),
+                    _ => return Err(RelicError::Parse(index, ParseError::InvalidTree)),
                 });
+            } else if content[0] == "|" {
+                // | .%2Fsrc content.rs
+                let [_, parent, name] = *content.as_slice() else {
+                    return Err(RelicError::Parse(index, ParseError::InvalidBlobHeader));
+                };
+
+                previous_blob = Some((decode(index, parent)?, decode(index, name)?));
             } else {
-                if content[0] == "|" {
-                    // | .%2Fsrc content.rs
-                    let [_, parent, name] = *content.as_slice() else {
-                        println!("invalid blob header");
-                        return None;
-                    };
-
-                    previous_blob = Some((parent.to_string(), name.to_string()));
-                } else {
-                    // + 0 "use std::{collections::{HashMap, HashSet}, fs, path::{Path, PathBuf}, sync::{Arc, Mutex}};"
-                    if content.len() < 2 {
-                        println!("invalid change line");
-                        return None;
-                    }
-
-                    let species = content[0];
-                    let line = match content[1].parse::<usize>() {
-                        Ok(i) => i,
-                        _ => {
-                            println!("invalid line index");
-                            return None;
-                        }
-                    };
-
-                    match &previous_blob {
-                        Some((p, n)) => {
-                            let decoded_path = urlencoding::decode(p).unwrap().to_string();
-                            let decoded_name = urlencoding::decode(n).unwrap().to_string();
-                            let s = unescape::unescape(&content[2..].join(" ")).unwrap();
-                            let content_text = s[1..s.len() - 1].to_string();
-
-                            match species {
-                                "+" => {
-                                    result.blobs.push(modifications::Blob::Create(
-                                        decoded_path,
-                                        decoded_name,
-                                        line,
-                                        content_text,
-                                    ));
-                                }
-                                "-" => {
-                                    result.blobs.push(modifications::Blob::Delete(
-                                        decoded_path,
-                                        decoded_name,
-                                        line,
-                                        content_text,
-                                    ));
-                                }
-                                _ => {
-                                    return None;
-                                }
-                            }
-                        }
-                        None => {
-                            return None;
-                        }
-                    }
+                // + 0 "use std::{collections::{HashMap, HashSet}, fs, path::{Path, PathBuf}, sync::{Arc, Mutex}};"
+                if content.len() < 3 {
+                    return Err(RelicError::Parse(index, ParseError::InvalidChange));
                 }
+
+                let Ok(line) = content[1].parse::<usize>() else {
+                    return Err(RelicError::Parse(index, ParseError::InvalidLineIndex));
+                };
+
+                let Some((path, name)) = previous_blob.clone() else {
+                    return Err(RelicError::Parse(index, ParseError::MissingBlobHeader));
+                };
+
+                // changes written before json escaping used rust debug escapes, eg : \u{1b}
+                let text = content[2..].join(" ");
+                let Some(text) = serde_json::from_str::<String>(&text)
+                    .ok()
+                    .or_else(|| utils::from_debug_string(&text))
+                else {
+                    return Err(RelicError::Parse(index, ParseError::InvalidEncoding));
+                };
+
+                result.blobs.push(match content[0] {
+                    "+" => modifications::Blob::Create(path, name, line, text),
+                    "-" => modifications::Blob::Delete(path, name, line, text),
+                    _ => return Err(RelicError::Parse(index, ParseError::InvalidChange)),
+                });
             }
         }
 
-        Some(result)
+        Ok(result)
     }
 }
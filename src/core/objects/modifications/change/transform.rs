@@ -0,0 +1,280 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{
+    modifications::{self, Hunk},
+    Content, Tree,
+};
+
+use super::Change;
+
+type BlobMap = HashMap<String, HashMap<String, Vec<modifications::Blob>>>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Conflict {
+    // both changes touch the same file or directory
+    Tree(
+        String, // parent directory
+        String, // name
+    ),
+    // both changes touch the same region of a file
+    Blob(
+        String, // parent directory
+        String, // file name
+        usize,  // line (before either change)
+    ),
+}
+
+impl Conflict {
+    pub fn describe(&self) -> String {
+        match self {
+            Conflict::Tree(p, n) => PathBuf::from(p).join(n).to_string_lossy().to_string(),
+            Conflict::Blob(p, n, l) => format!(
+                "{} (line {})",
+                PathBuf::from(p).join(n).to_string_lossy(),
+                l + 1
+            ),
+        }
+    }
+}
+
+impl Change {
+    pub fn verify(&self, base: &Tree) -> Result<(), Vec<Conflict>> {
+        // checks that the change can be applied to base as is
+        // (everything it deletes exists, nothing it creates already does, deleted lines match)
+        let mut conflicts = BTreeSet::new();
+
+        let full_path = |p: &String, n: &String| PathBuf::from(p).join(n);
+
+        for t in &self.trees {
+            let (p, n) = t.extract_data();
+            let existing = base.get(&full_path(&p, &n));
+            let valid = match t {
+                modifications::Tree::CreateBlob(_, _) | modifications::Tree::CreateTree(_, _) => {
+                    // allowed if the existing entry is replaced in the same change
+                    match existing {
+                        None => true,
+                        Some(Content::Blob(_)) => self
+                            .trees
+                            .contains(&modifications::Tree::DeleteBlob(p.clone(), n.clone())),
+                        Some(Content::Tree(_)) => self
+                            .trees
+                            .contains(&modifications::Tree::DeleteTree(p.clone(), n.clone())),
+                    }
+                }
+                modifications::Tree::DeleteBlob(_, _) => matches!(existing, Some(Content::Blob(_))),
+                modifications::Tree::DeleteTree(_, _) => matches!(existing, Some(Content::Tree(_))),
+            };
+            if !valid {
+                conflicts.insert(Conflict::Tree(p, n));
+            }
+        }
+
+        for (path, names) in self.as_map().1 {
+            for (name, modifications) in names {
+                let lines = match base.get(&full_path(&path, &name)) {
+                    Some(Content::Blob(b)) => b
+                        .content
+                        .split("\n")
+                        .map(|x| x.to_string())
+                        .collect::<Vec<String>>(),
+                    _ if self
+                        .trees
+                        .contains(&modifications::Tree::CreateBlob(path.clone(), name.clone())) =>
+                    {
+                        vec!["".to_string()]
+                    }
+                    _ => {
+                        conflicts.insert(Conflict::Tree(path.clone(), name.clone()));
+                        continue;
+                    }
+                };
+
+                let (deleted, created) = Hunk::split_modifications(&modifications);
+                for (l, text) in &deleted {
+                    if lines.get(*l) != Some(text) {
+                        conflicts.insert(Conflict::Blob(path.clone(), name.clone(), *l));
+                    }
+                }
+
+                // creations are inserted in order, each one must land within the file
+                let length = lines.len() - deleted.len().min(lines.len());
+                if let Some((_, l)) = created
+                    .keys()
+                    .enumerate()
+                    .find(|(i, l)| **l > length + i)
+                {
+                    conflicts.insert(Conflict::Blob(path.clone(), name.clone(), *l));
+                }
+            }
+        }
+
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(conflicts.into_iter().collect())
+        }
+    }
+
+    pub fn transform(&self, over: &Change) -> Result<Change, Vec<Conflict>> {
+        // rebases self over a concurrent change
+        //
+        // self : x -> y
+        // over : x -> z
+        // result : z -> (y + z)
+        //
+        // independent edits are shifted so that they commute
+        // edits to the same region are reported as conflicts
+
+        let mut conflicts = BTreeSet::new();
+
+        let (_, self_map) = self.as_map();
+        let (_, over_map) = over.as_map();
+
+        let full_path = |p: &String, n: &String| PathBuf::from(p).join(n);
+        let modifications_of = |map: &BlobMap, p: &String, n: &String| {
+            let mut m = map
+                .get(p)
+                .and_then(|m| m.get(n))
+                .cloned()
+                .unwrap_or_default();
+            m.sort();
+            m
+        };
+
+        // whether `change` does anything under `full` that `other` does not also do
+        let diverges_under =
+            |change: &Change, map: &BlobMap, other: &Change, other_map: &BlobMap, full: &Path| {
+                change.trees.iter().any(|t| {
+                    let (p, n) = t.extract_data();
+                    full_path(&p, &n).starts_with(full) && !other.trees.contains(t)
+                }) || map.iter().any(|(p, names)| {
+                    names.keys().any(|n| {
+                        full_path(p, n).starts_with(full)
+                            && modifications_of(map, p, n) != modifications_of(other_map, p, n)
+                    })
+                })
+            };
+
+        // #region containers
+        let mut trees = vec![];
+        for t in &self.trees {
+            if over.trees.contains(t) {
+                // already applied
+                continue;
+            }
+
+            let (p, n) = t.extract_data();
+            let conflicting = match t {
+                modifications::Tree::CreateBlob(_, _) | modifications::Tree::CreateTree(_, _) => {
+                    // the same name was created with a different type
+                    // or the parent directory no longer exists
+                    over.trees.iter().any(|o| match o {
+                        modifications::Tree::CreateBlob(op, on)
+                        | modifications::Tree::CreateTree(op, on) => (op, on) == (&p, &n),
+                        modifications::Tree::DeleteTree(op, on) => {
+                            Path::new(&p).starts_with(full_path(op, on))
+                        }
+                        _ => false,
+                    })
+                }
+                modifications::Tree::DeleteBlob(_, _) | modifications::Tree::DeleteTree(_, _) => {
+                    // deleting something the other change still relies on
+                    diverges_under(over, &over_map, self, &self_map, &full_path(&p, &n))
+                }
+            };
+
+            if conflicting {
+                conflicts.insert(Conflict::Tree(p, n));
+            } else {
+                trees.push(t.clone());
+            }
+        }
+
+        // editing something the other change deleted
+        for o in &over.trees {
+            if let modifications::Tree::DeleteBlob(op, on)
+            | modifications::Tree::DeleteTree(op, on) = o
+            {
+                if !self.trees.contains(o)
+                    && diverges_under(self, &self_map, over, &over_map, &full_path(op, on))
+                {
+                    conflicts.insert(Conflict::Tree(op.clone(), on.clone()));
+                }
+            }
+        }
+        // #endregion
+
+        // #region blobs
+        let mut files = BTreeSet::new();
+        for (path, names) in &self_map {
+            for name in names.keys() {
+                files.insert((path.clone(), name.clone()));
+            }
+        }
+
+        let mut blobs = vec![];
+        for (path, name) in files {
+            let self_hunks = Hunk::from_modifications(&self_map[&path][&name]);
+            let over_hunks = over_map
+                .get(&path)
+                .and_then(|m| m.get(&name))
+                .map_or(vec![], |m| Hunk::from_modifications(m));
+
+            let mut hunks = vec![];
+            for h in self_hunks {
+                if over_hunks.contains(&h) {
+                    // both made the exact same edit
+                    continue;
+                }
+
+                // hunks conflict when they overlap or touch
+                if over_hunks
+                    .iter()
+                    .any(|o| o.start <= h.end() && h.start <= o.end())
+                {
+                    conflicts.insert(Conflict::Blob(path.clone(), name.clone(), h.start));
+                    continue;
+                }
+
+                let shift = over_hunks
+                    .iter()
+                    .filter(|o| o.end() < h.start)
+                    .map(|o| o.inserted.len() as i64 - o.deleted.len() as i64)
+                    .sum::<i64>();
+
+                hunks.push(Hunk {
+                    start: (h.start as i64 + shift) as usize,
+                    ..h
+                });
+            }
+
+            blobs.append(&mut Hunk::into_modifications(&hunks, &path, &name));
+        }
+        // #endregion
+
+        if conflicts.is_empty() {
+            Ok(Change { trees, blobs })
+        } else {
+            Err(conflicts.into_iter().collect())
+        }
+    }
+
+    pub fn rebase(changes: &[Change], over: &Change) -> Result<Vec<Change>, Vec<Conflict>> {
+        // rebases a sequence of changes (eg : pending commits) over a concurrent change
+        // `over` is carried along so that every change is transformed against
+        // a version of it that shares the same base
+        let mut over = over.clone();
+        let mut result = vec![];
+        for c in changes {
+            let rebased = c.transform(&over)?;
+            over = over.transform(c)?;
+            result.push(rebased);
+        }
+        Ok(result)
+    }
+}
@@ -33,6 +33,15 @@ impl Tree {
         }
     }
 
+    pub fn inverse(&self) -> Tree {
+        match self {
+            Tree::CreateBlob(p, n) => Tree::DeleteBlob(p.to_string(), n.to_string()),
+            Tree::CreateTree(p, n) => Tree::DeleteTree(p.to_string(), n.to_string()),
+            Tree::DeleteBlob(p, n) => Tree::CreateBlob(p.to_string(), n.to_string()),
+            Tree::DeleteTree(p, n) => Tree::CreateTree(p.to_string(), n.to_string()),
+        }
+    }
+
     pub fn serialise(&self) -> String {
         format!(
             "{} {}",
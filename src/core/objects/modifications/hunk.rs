@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use crate::core::modifications;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    // contiguous region of a file, described relative to the old content
+    pub start: usize,          // first affected line (old file)
+    pub deleted: Vec<String>,  // lines removed, starting at `start`
+    pub inserted: Vec<String>, // lines inserted in their place
+}
+
+impl Hunk {
+    pub fn end(&self) -> usize {
+        // one past the last removed line (old file)
+        self.start + self.deleted.len()
+    }
+
+    pub fn trim(&mut self) {
+        // drop lines that are deleted and inserted again unchanged
+        while !self.deleted.is_empty()
+            && !self.inserted.is_empty()
+            && self.deleted[0] == self.inserted[0]
+        {
+            self.deleted.remove(0);
+            self.inserted.remove(0);
+            self.start += 1;
+        }
+        while !self.deleted.is_empty()
+            && !self.inserted.is_empty()
+            && self.deleted.last() == self.inserted.last()
+        {
+            self.deleted.pop();
+            self.inserted.pop();
+        }
+    }
+
+    pub fn header(&self, offset: i64) -> String {
+        // 1-indexed, offset is the shift in line numbers caused by the hunks before
+        format!(
+            "@@ -{},{} +{},{} @@",
+            self.start + 1,
+            self.deleted.len(),
+            self.start as i64 + offset + 1,
+            self.inserted.len()
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deleted.is_empty() && self.inserted.is_empty()
+    }
+
+    pub fn split_modifications(
+        modifications: &[modifications::Blob],
+    ) -> (BTreeMap<usize, String>, BTreeMap<usize, String>) {
+        // (deletions keyed by old line, creations keyed by new line)
+        let mut deleted = BTreeMap::new();
+        let mut created = BTreeMap::new();
+        for m in modifications {
+            match m {
+                modifications::Blob::Delete(_, _, l, t) => {
+                    deleted.insert(*l, t.clone());
+                }
+                modifications::Blob::Create(_, _, l, t) => {
+                    created.insert(*l, t.clone());
+                }
+            }
+        }
+        (deleted, created)
+    }
+
+    pub fn from_modifications(modifications: &[modifications::Blob]) -> Vec<Hunk> {
+        // modifications of a single file
+        let (deleted, created) = Hunk::split_modifications(modifications);
+        Hunk::from_maps(&deleted, &created)
+    }
+
+    pub fn from_maps(
+        deleted: &BTreeMap<usize, String>,
+        created: &BTreeMap<usize, String>,
+    ) -> Vec<Hunk> {
+        // walk the old (x) and new (y) files side by side
+        // every line is either deleted (x only), created (y only) or kept (both)
+        let last_x = deleted.keys().next_back().map_or(0, |l| l + 1);
+        let last_y = created.keys().next_back().map_or(0, |l| l + 1);
+
+        let mut result: Vec<Hunk> = vec![];
+        let (mut x, mut y) = (0, 0);
+        while x < last_x || y < last_y {
+            let (deletion, creation) = match (deleted.get(&x), created.get(&y)) {
+                (Some(t), _) => (Some(t.clone()), None),
+                (None, Some(t)) => (None, Some(t.clone())),
+                (None, None) => {
+                    x += 1;
+                    y += 1;
+                    continue;
+                }
+            };
+
+            let hunk = match result.last_mut() {
+                Some(h) if h.end() == x => h,
+                _ => {
+                    result.push(Hunk {
+                        start: x,
+                        deleted: vec![],
+                        inserted: vec![],
+                    });
+                    result.last_mut().unwrap()
+                }
+            };
+
+            if let Some(t) = deletion {
+                hunk.deleted.push(t);
+                x += 1;
+            }
+            if let Some(t) = creation {
+                hunk.inserted.push(t);
+                y += 1;
+            }
+        }
+
+        result
+    }
+
+    pub fn into_modifications(hunks: &[Hunk], path: &str, name: &str) -> Vec<modifications::Blob> {
+        // hunks must be sorted and non-overlapping
+        let mut result = vec![];
+        let mut offset: i64 = 0;
+        for h in hunks {
+            for (i, t) in h.deleted.iter().enumerate() {
+                result.push(modifications::Blob::Delete(
+                    path.to_string(),
+                    name.to_string(),
+                    h.start + i,
+                    t.clone(),
+                ));
+            }
+            let new_start = (h.start as i64 + offset) as usize;
+            for (i, t) in h.inserted.iter().enumerate() {
+                result.push(modifications::Blob::Create(
+                    path.to_string(),
+                    name.to_string(),
+                    new_start + i,
+                    t.clone(),
+                ));
+            }
+            offset += h.inserted.len() as i64 - h.deleted.len() as i64;
+        }
+        result
+    }
+}
@@ -1,7 +1,9 @@
 pub mod change;
 pub mod container;
 pub mod blob;
+pub mod hunk;
 
-pub use change::Change;
+pub use change::{Change, Conflict};
 pub use container::Tree;
 pub use blob::Blob;
+pub use hunk::Hunk;
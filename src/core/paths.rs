@@ -1,10 +1,16 @@
 pub const RELIC_PATH_PARENT: &str = ".relic";
 pub const RELIC_PATH_HISTORY: &str = ".relic/history";
 pub const RELIC_PATH_PENDING: &str = ".relic/pending";
+pub const RELIC_PATH_BRANCHES: &str = ".relic/branches";
+pub const RELIC_PATH_STASH: &str = ".relic/stash";
+pub const RELIC_PATH_TAGS: &str = ".relic/tags";
+pub const RELIC_PATH_REMOTE: &str = ".relic/remote";
 
 pub const RELIC_PATH_ROOT: &str = ".relic/root";
 pub const RELIC_PATH_INFO: &str = ".relic/info.json";
 pub const RELIC_PATH_TRACKED: &str = ".relic/tracked";
+pub const RELIC_PATH_INDEX: &str = ".relic/index";
 pub const RELIC_PATH_UPSTREAM: &str = ".relic/upstream";
+pub const RELIC_PATH_BISECT: &str = ".relic/bisect";
 
 pub const RELIC_PATH_IGNORE: &str = ".relic_ignore";
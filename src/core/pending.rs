@@ -0,0 +1,293 @@
+use std::path::{Path, PathBuf};
+
+use crate::core::{commit::Commit, error::RelicError, modifications::Change, utils, State};
+
+impl State {
+    // #region pending rewrites
+    // pending commits are rewritten in place, later commits are rebased so that they still apply
+    pub fn pending_amend(
+        &mut self,
+        message: Option<String>,
+        description: Option<String>,
+    ) -> Result<Commit, RelicError> {
+        // folds the staged changes into the latest pending commit
+        if self.info.detached.is_some() {
+            return Err(RelicError::Detached);
+        }
+        let Some(last) = self.pending_get().pop() else {
+            return Err(RelicError::CommitNotFound);
+        };
+        let staged = self.index_get();
+        if staged.is_empty() && message.is_none() && description.is_none() {
+            return Err(RelicError::NoChanges);
+        }
+
+        let commit = Commit {
+            message: message.unwrap_or(last.message.clone()),
+            description: description.unwrap_or(last.description.clone()),
+            change: Change::compose(&last.change, &staged),
+            ..last.clone()
+        };
+        self.pending_remove(&last);
+        self.pending_add(commit.clone());
+        self.upstream_apply(&staged);
+        self.index_clear();
+
+        Ok(commit)
+    }
+
+    pub fn pending_reword(
+        &mut self,
+        index: usize,
+        message: Option<String>,
+        description: Option<String>,
+    ) -> Result<Commit, RelicError> {
+        let pending = self.pending_get();
+        let Some(c) = pending.get(index) else {
+            return Err(RelicError::CommitNotFound);
+        };
+
+        let commit = Commit {
+            message: message.unwrap_or(c.message.clone()),
+            description: description.unwrap_or(c.description.clone()),
+            ..c.clone()
+        };
+        let mut commits = vec![commit.clone()];
+        commits.extend_from_slice(&pending[index + 1..]);
+        self.pending_rewrite(index, commits)?;
+        Ok(commit)
+    }
+
+    pub fn pending_drop(&mut self, index: usize) -> Result<Commit, RelicError> {
+        let pending = self.pending_get();
+        let Some(dropped) = pending.get(index) else {
+            return Err(RelicError::CommitNotFound);
+        };
+
+        let later = State::pending_without(&pending, index)?;
+        self.pending_rewrite(index, later)?;
+        Ok(dropped.clone())
+    }
+
+    pub fn pending_move(&mut self, from: usize, to: usize) -> Result<(), RelicError> {
+        let pending = self.pending_get();
+        if from >= pending.len() || to >= pending.len() {
+            return Err(RelicError::CommitNotFound);
+        }
+        if from == to {
+            return Ok(());
+        }
+
+        // take the commit out, then move it over the commits between its old and new position
+        let moved = &pending[from];
+        let mut rest = pending[..from].to_vec();
+        rest.append(&mut State::pending_without(&pending, from)?);
+
+        let start = from.min(to);
+        let between = Change::compose_all(
+            &rest[from.min(to)..from.max(to)]
+                .iter()
+                .map(|c| c.change.clone())
+                .collect::<Vec<Change>>(),
+        );
+        let over = if to > from {
+            between
+        } else {
+            between.inverse()
+        };
+        let change = moved
+            .change
+            .transform(&over)
+            .map_err(RelicError::Conflicts)?;
+        let tail = Change::rebase(
+            &rest[to..]
+                .iter()
+                .map(|c| c.change.clone())
+                .collect::<Vec<Change>>(),
+            &change,
+        )
+        .map_err(RelicError::Conflicts)?;
+
+        let mut commits = rest[..to].to_vec();
+        commits.push(Commit {
+            change,
+            ..moved.clone()
+        });
+        for (c, change) in rest[to..].iter().zip(tail) {
+            commits.push(Commit {
+                change,
+                ..c.clone()
+            });
+        }
+
+        // commits keep their timestamp, only the order of the queue changes
+        self.pending_rewrite(start, commits[start..].to_vec())
+    }
+
+    pub fn pending_squash(&mut self, index: usize) -> Result<Commit, RelicError> {
+        // folds a commit into the one before it
+        let pending = self.pending_get();
+        if index == 0 || index >= pending.len() {
+            return Err(RelicError::CommitNotFound);
+        }
+
+        let (previous, c) = (&pending[index - 1], &pending[index]);
+        let commit = Commit {
+            change: Change::compose(&previous.change, &c.change),
+            ..previous.clone()
+        };
+        let mut commits = vec![commit.clone()];
+        commits.extend_from_slice(&pending[index + 1..]);
+        self.pending_rewrite(index - 1, commits)?;
+        // whatever referred to the folded commit now refers to the result
+        self.tag_retarget(c.timestamp, commit.timestamp);
+        self.bisect_retarget(c.timestamp, commit.timestamp);
+        Ok(commit)
+    }
+
+    pub fn pending_squash_recent(
+        &mut self,
+        count: usize,
+        message: Option<String>,
+        description: Option<String>,
+    ) -> Result<Commit, RelicError> {
+        // folds the most recent count commits into the oldest of them, one at a time
+        let length = self.pending_get().len();
+        if count < 2 || count > length {
+            return Err(RelicError::CommitNotFound);
+        }
+
+        let start = length - count;
+        let mut commit = self.pending_get()[start].clone();
+        for _ in 1..count {
+            commit = self.pending_squash(start + 1)?;
+        }
+        if message.is_some() || description.is_some() {
+            commit = self.pending_reword(start, message, description)?;
+        }
+        Ok(commit)
+    }
+
+    pub fn pending_split(
+        &mut self,
+        index: usize,
+        paths: &[PathBuf],
+        message: String,
+        rest_message: Option<String>,
+    ) -> Result<(Commit, Commit), RelicError> {
+        // splits the changes at the given paths off into their own commit, right before the rest
+        // files are never split, so line numbers stay valid in both halves
+        let pending = self.pending_get();
+        let Some(c) = pending.get(index) else {
+            return Err(RelicError::CommitNotFound);
+        };
+
+        let paths = paths
+            .iter()
+            .map(|p| PathBuf::from(".").join(p))
+            .collect::<Vec<PathBuf>>();
+        let split = c.change.filter_paths(&paths);
+        let mut rest = c
+            .change
+            .filter_by(|path| !paths.iter().any(|p| path.starts_with(p)));
+        // directories created by the first half already exist for the second
+        rest.trees.retain(|t| !split.trees.contains(t));
+        if split.is_empty() || rest.is_empty() {
+            return Err(RelicError::NoChanges);
+        }
+
+        let first = Commit {
+            message,
+            change: split,
+            ..c.clone()
+        };
+        // the second half is a new commit, references to the split commit stay on the first
+        let second = Commit {
+            message: rest_message.unwrap_or(c.message.clone()),
+            change: rest,
+            timestamp: self.pending_timestamp(),
+            ..c.clone()
+        };
+
+        let mut commits = vec![first.clone(), second.clone()];
+        commits.extend_from_slice(&pending[index + 1..]);
+        self.pending_rewrite(index, commits)?;
+        Ok((first, second))
+    }
+
+    fn pending_timestamp(&self) -> u64 {
+        // timestamps identify commits, a new one must not be taken by any commit of the branch
+        let latest = self
+            .branch_commits(&self.info.branch)
+            .iter()
+            .map(|c| c.timestamp + 1)
+            .max()
+            .unwrap_or(0);
+        utils::get_time().max(latest)
+    }
+
+    fn pending_without(pending: &[Commit], index: usize) -> Result<Vec<Commit>, RelicError> {
+        // the commits after index, rebased as if the commit at index never happened
+        let later = &pending[index + 1..];
+        let changes = Change::rebase(
+            &later
+                .iter()
+                .map(|c| c.change.clone())
+                .collect::<Vec<Change>>(),
+            &pending[index].change.inverse(),
+        )
+        .map_err(RelicError::Conflicts)?;
+
+        Ok(later
+            .iter()
+            .zip(changes)
+            .map(|(c, change)| Commit {
+                change,
+                ..c.clone()
+            })
+            .collect())
+    }
+
+    fn pending_rewrite(&mut self, from: usize, commits: Vec<Commit>) -> Result<(), RelicError> {
+        // replaces every pending commit from index onwards with commits
+        // if upstream ends up different, the working tree is moved along with it
+        if self.info.detached.is_some() {
+            return Err(RelicError::Detached);
+        }
+        let pending = self.pending_get();
+
+        let mut target = self.upstream.clone();
+        for c in pending[from..].iter().rev() {
+            target.unapply_changes(&c.change);
+        }
+        for c in &commits {
+            target.apply_changes(&c.change);
+        }
+
+        let change = Change::get_change_all(&self.upstream, &target, Path::new(&self.path));
+        if !change.is_empty() {
+            if !self.get_tracked_changes().is_empty() || !self.index_get().is_empty() {
+                return Err(RelicError::UncommittedChanges);
+            }
+            let collisions = self.untracked_collisions(&change, &self.upstream);
+            if !collisions.is_empty() {
+                return Err(RelicError::UntrackedOverwritten(collisions));
+            }
+        }
+
+        for c in &pending[from..] {
+            self.pending_remove(c);
+        }
+        self.pending_order_set(&[pending[..from].to_vec(), commits.clone()].concat());
+        for c in commits {
+            self.pending_add(c);
+        }
+
+        if !change.is_empty() {
+            self.upstream_apply(&change);
+            self.apply_to_working_tree(&change, &target);
+        }
+        Ok(())
+    }
+    // #endregion
+}
@@ -8,12 +8,16 @@ use crate::core::{error::RelicError, paths::RELIC_PATH_INFO, state};
 pub struct RelicInfo {
     pub remote: String,
     pub branch: String,
+    // timestamp of the commit checked out by `relic cherry`, none when at the branch tip
+    #[serde(default)]
+    pub detached: Option<u64>,
 }
 impl RelicInfo {
     pub fn empty() -> RelicInfo {
         RelicInfo {
             remote: "".to_string(),
             branch: "".to_string(),
+            detached: None,
         }
     }
 
@@ -21,6 +25,7 @@ impl RelicInfo {
         RelicInfo {
             remote: "".to_string(),
             branch: state::DEFAULT_BRANCH.to_string(),
+            detached: None,
         }
     }
 
@@ -39,4 +44,8 @@ impl RelicInfo {
     pub fn serialise(&self) -> String {
         serde_json::to_string(self).unwrap()
     }
+
+    pub fn save(&self) {
+        let _ = fs::write(RELIC_PATH_INFO, self.serialise());
+    }
 }
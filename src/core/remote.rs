@@ -0,0 +1,368 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::core::{
+    commit::Commit,
+    content_set,
+    error::RelicError,
+    modifications::Change,
+    objects::data::Upstream,
+    paths::{
+        RELIC_PATH_BRANCHES, RELIC_PATH_HISTORY, RELIC_PATH_IGNORE, RELIC_PATH_PENDING,
+        RELIC_PATH_REMOTE, RELIC_PATH_STASH, RELIC_PATH_UPSTREAM,
+    },
+    RelicInfo, State, Tree,
+};
+
+pub fn remote_root(url: &str) -> Result<PathBuf, RelicError> {
+    // remotes are other repositories on disk, as a path or a file:// url
+    let path = PathBuf::from(url.strip_prefix("file://").unwrap_or(url));
+    match path.canonicalize() {
+        Ok(p) if p.join(".relic").is_dir() => Ok(p),
+        _ => Err(RelicError::RemoteNotFound),
+    }
+}
+
+impl State {
+    // #region remote
+    fn at_remote<T, F>(url: &str, f: F) -> Result<T, RelicError>
+    where
+        F: FnOnce(&mut State) -> Result<T, RelicError>,
+    {
+        // every relic path is relative to the repository root, so the remote is worked on from inside it
+        // the working directory is restored on the way out, whatever happens in f
+        struct Restore(PathBuf);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let _ = env::set_current_dir(&self.0);
+            }
+        }
+
+        let root = remote_root(url)?;
+        let _restore = Restore(env::current_dir().map_err(|_| RelicError::FileCantOpen)?);
+        env::set_current_dir(&root).map_err(|_| RelicError::RemoteNotFound)?;
+
+        match State::create(PathBuf::from(".")) {
+            Ok(mut remote) => f(&mut remote),
+            Err(_) => Err(RelicError::RemoteNotFound),
+        }
+    }
+
+    pub fn remote_set(&mut self, url: &str) -> Result<String, RelicError> {
+        // stored as an absolute path, so it still resolves from clones elsewhere
+        let root = remote_root(url)?.to_string_lossy().to_string();
+        self.info.remote = root.clone();
+        self.info.save();
+        Ok(root)
+    }
+
+    fn remote_url(&self) -> Result<String, RelicError> {
+        if self.info.remote.is_empty() {
+            return Err(RelicError::RemoteNotSet);
+        }
+        Ok(self.info.remote.clone())
+    }
+
+    fn is_prefix(a: &[Commit], b: &[Commit]) -> bool {
+        a.len() <= b.len() && a.iter().zip(b).all(|(x, y)| x.timestamp == y.timestamp)
+    }
+
+    fn history_write(&self, name: &str, commit: &Commit) -> Result<(), RelicError> {
+        let directory = self.branch_path(name, RELIC_PATH_HISTORY);
+        let _ = fs::create_dir_all(&directory);
+        fs::write(
+            format!("{directory}/{}.diff", commit.timestamp),
+            commit.serialise(),
+        )
+        .map_err(|_| RelicError::FileCantOpen)
+    }
+
+    fn branch_import(&self, name: &str, commits: &[Commit]) -> Result<(), RelicError> {
+        // creates a parked branch holding only the given history
+        let mut tree = Tree::new();
+        for c in commits {
+            tree.apply_changes(&c.change);
+        }
+
+        let _ = fs::create_dir_all(format!("{RELIC_PATH_BRANCHES}/{name}"));
+        for content in [RELIC_PATH_PENDING, RELIC_PATH_HISTORY, RELIC_PATH_STASH] {
+            let _ = fs::create_dir_all(self.branch_path(name, content));
+        }
+        for c in commits {
+            self.history_write(name, c)?;
+        }
+        fs::write(
+            self.branch_path(name, RELIC_PATH_UPSTREAM),
+            Upstream::from_tree(&tree).serialise(),
+        )
+        .map_err(|_| RelicError::FileCantOpen)
+    }
+
+    pub fn fetched_history(&self, name: &str) -> Vec<Commit> {
+        // the history of a remote branch, as of the last fetch
+        let mut result = State::commits_in(&format!("{RELIC_PATH_REMOTE}/{name}"));
+        result.sort_by_key(|c| c.id);
+        result
+    }
+
+    pub fn fetch(&mut self) -> Result<Vec<(String, usize)>, RelicError> {
+        // copies the history of every remote branch to RELIC_PATH_REMOTE, along with any new tags
+        // returns the number of commits each branch has that the local one does not
+        let url = self.remote_url()?;
+        let (branches, tags) = State::at_remote(&url, |remote| {
+            Ok((
+                remote
+                    .branch_list()
+                    .into_iter()
+                    .map(|b| {
+                        let history = remote.branch_history(&b);
+                        (b, history)
+                    })
+                    .collect::<Vec<(String, Vec<Commit>)>>(),
+                remote.tag_get(),
+            ))
+        })?;
+
+        // written next to the previous copy, which is only replaced once the new one is complete
+        let staging = format!("{RELIC_PATH_REMOTE}.tmp");
+        let _ = fs::remove_dir_all(&staging);
+        let mut result = vec![];
+        for (name, history) in branches {
+            let directory = format!("{staging}/{name}");
+            fs::create_dir_all(&directory).map_err(|_| RelicError::FileCantOpen)?;
+            for c in &history {
+                fs::write(format!("{directory}/{}.diff", c.timestamp), c.serialise())
+                    .map_err(|_| RelicError::FileCantOpen)?;
+            }
+
+            let local = if self.branch_exists(&name) {
+                self.branch_history(&name)
+            } else {
+                vec![]
+            };
+            let new = history
+                .iter()
+                .filter(|c| !local.iter().any(|l| l.timestamp == c.timestamp))
+                .count();
+            result.push((name, new));
+        }
+        fs::create_dir_all(&staging).map_err(|_| RelicError::FileCantOpen)?;
+        if Path::new(RELIC_PATH_REMOTE).exists() {
+            fs::remove_dir_all(RELIC_PATH_REMOTE).map_err(|_| RelicError::FileCantOpen)?;
+        }
+        fs::rename(&staging, RELIC_PATH_REMOTE).map_err(|_| RelicError::FileCantOpen)?;
+
+        for t in tags {
+            self.tag_import(&t);
+        }
+        Ok(result)
+    }
+
+    pub fn pull(&mut self) -> Result<Vec<Commit>, RelicError> {
+        // fetches, then adds the new remote commits of the current branch to its history
+        // pending commits are rebased on top of them
+        if self.info.detached.is_some() {
+            return Err(RelicError::Detached);
+        }
+        let name = self.info.branch.clone();
+        if !self.fetch()?.iter().any(|(b, _)| *b == name) {
+            return Err(RelicError::BranchNotFound);
+        }
+
+        let remote = self.fetched_history(&name);
+        let local = self.history_get();
+        if !State::is_prefix(&local, &remote) {
+            return Err(RelicError::RemoteDiverged);
+        }
+        let new = remote[local.len()..].to_vec();
+        if new.is_empty() {
+            return Ok(new);
+        }
+
+        let pending = self.pending_get();
+        let mut target = self.upstream.clone();
+        for c in pending.iter().rev() {
+            target.unapply_changes(&c.change);
+        }
+        let over = Change::compose_all(
+            &new.iter()
+                .map(|c| c.change.clone())
+                .collect::<Vec<Change>>(),
+        );
+        let rebased = Change::rebase(
+            &pending
+                .iter()
+                .map(|c| c.change.clone())
+                .collect::<Vec<Change>>(),
+            &over,
+        )
+        .map_err(RelicError::Conflicts)?;
+        for c in &new {
+            target.apply_changes(&c.change);
+        }
+        for change in &rebased {
+            target.apply_changes(change);
+        }
+
+        let change = Change::get_change_all(&self.upstream, &target, Path::new(&self.path));
+        if !change.is_empty() {
+            if !self.get_tracked_changes().is_empty() || !self.index_get().is_empty() {
+                return Err(RelicError::UncommittedChanges);
+            }
+            let collisions = self.untracked_collisions(&change, &self.upstream);
+            if !collisions.is_empty() {
+                return Err(RelicError::UntrackedOverwritten(collisions));
+            }
+        }
+
+        for c in &new {
+            self.history_write(&name, c)?;
+        }
+        for (c, change) in pending.iter().zip(rebased) {
+            self.pending_remove(c);
+            self.pending_add(Commit {
+                change,
+                ..c.clone()
+            });
+        }
+
+        self.upstream_apply(&change);
+        self.apply_to_working_tree(&change, &target);
+        self.track_add(&State::created_paths(&over));
+        Ok(new)
+    }
+
+    pub fn push(&mut self) -> Result<Vec<Commit>, RelicError> {
+        // publishes the pending commits of the current branch, they become history on both sides
+        // the remote has to be up to date with our history, pull first otherwise
+        // returns every commit the remote did not have, history included
+        if self.info.detached.is_some() {
+            return Err(RelicError::Detached);
+        }
+        let url = self.remote_url()?;
+        let name = self.info.branch.clone();
+        let local = self.history_get();
+        let pending = self.pending_get();
+
+        let next = local.last().and_then(|c| c.id).map_or(0, |i| i + 1);
+        let published = pending
+            .iter()
+            .enumerate()
+            .map(|(i, c)| Commit {
+                id: Some(next + i as u32),
+                ..c.clone()
+            })
+            .collect::<Vec<Commit>>();
+        let tags = self.tag_get();
+
+        let sent = State::at_remote(&url, |remote| {
+            if !remote.branch_exists(&name) {
+                remote.branch_import(&name, &[])?;
+            }
+            let history = remote.branch_history(&name);
+            if !State::is_prefix(&history, &local) {
+                return Err(RelicError::RemoteAhead);
+            }
+            if !remote.branch_pending(&name).is_empty() {
+                return Err(RelicError::RemoteBusy);
+            }
+
+            let sending = [local[history.len()..].to_vec(), published.clone()].concat();
+            let change = Change::compose_all(
+                &sending
+                    .iter()
+                    .map(|c| c.change.clone())
+                    .collect::<Vec<Change>>(),
+            );
+
+            let checked_out = name == remote.info.branch;
+            // the working tree of the remote is moved along, so it has to be clean
+            if checked_out
+                && (remote.info.detached.is_some()
+                    || !remote.get_tracked_changes().is_empty()
+                    || !remote.index_get().is_empty())
+            {
+                return Err(RelicError::RemoteBusy);
+            }
+            let mut upstream = if checked_out {
+                remote.upstream.clone()
+            } else {
+                remote.branch_upstream(&name)?
+            };
+            upstream.apply_changes(&change);
+
+            // upstream is written last, it is never ahead of the history it is made of
+            for c in &sending {
+                remote.history_write(&name, c)?;
+            }
+            for t in &tags {
+                remote.tag_import(t);
+            }
+            fs::write(
+                remote.branch_path(&name, RELIC_PATH_UPSTREAM),
+                Upstream::from_tree(&upstream).serialise(),
+            )
+            .map_err(|_| RelicError::FileCantOpen)?;
+
+            if checked_out {
+                remote.upstream = upstream.clone();
+                remote.apply_to_working_tree(&change, &upstream);
+                remote.track_add(&State::created_paths(&change));
+            }
+            Ok(sending)
+        })?;
+
+        // history first, pending commits are only removed once they are safely in it
+        for (c, p) in pending.iter().zip(&published) {
+            self.history_write(&name, p)?;
+            self.pending_remove(c);
+        }
+        self.pending_order_set(&[]);
+        Ok(sent)
+    }
+
+    pub fn clone_into(url: &str, directory: &Path) -> Result<State, RelicError> {
+        // creates a repository at directory holding every branch of the remote
+        // the remote's current branch is checked out
+        let root = remote_root(url)?;
+        let (current, branches) = State::at_remote(url, |remote| {
+            Ok((
+                remote.info.branch.clone(),
+                remote
+                    .branch_list()
+                    .into_iter()
+                    .map(|b| {
+                        let history = remote.branch_history(&b);
+                        (b, history)
+                    })
+                    .collect::<Vec<(String, Vec<Commit>)>>(),
+            ))
+        })?;
+
+        if fs::read_dir(directory).is_ok_and(|mut d| d.next().is_some()) {
+            return Err(RelicError::DirectoryNotEmpty);
+        }
+        fs::create_dir_all(directory).map_err(|_| RelicError::FileCantOpen)?;
+        env::set_current_dir(directory).map_err(|_| RelicError::FileCantOpen)?;
+
+        State::initialise_repository(&RelicInfo {
+            remote: root.to_string_lossy().to_string(),
+            branch: current.clone(),
+            detached: None,
+        });
+        let mut state = State::create(PathBuf::from("."))?;
+        for (name, history) in &branches {
+            if *name != current {
+                state.branch_import(name, history)?;
+            }
+        }
+        state.pull()?;
+        if !Path::new(RELIC_PATH_IGNORE).exists() {
+            let _ = fs::write(RELIC_PATH_IGNORE, content_set::DEFAULT_IGNORE);
+        }
+        Ok(state)
+    }
+    // #endregion
+}
@@ -0,0 +1,185 @@
+use crate::core::{commit::Commit, error::RelicError, utils, State};
+
+// a commit, along with the branch it was found on
+#[derive(Debug, Clone)]
+pub struct Revision {
+    pub branch: String,
+    pub commit: Commit,
+}
+
+impl State {
+    // #region revisions
+    pub fn branch_commits(&self, name: &str) -> Vec<Commit> {
+        // the line of a branch, oldest first
+        let mut commits = self.branch_history(name);
+        commits.append(&mut self.branch_pending(name));
+        commits
+    }
+
+    pub fn revision_parse(&self, reference: &str) -> Result<Revision, RelicError> {
+        self.revision_parse_in(&self.info.branch.clone(), reference)
+    }
+
+    pub fn revision_parse_in(&self, branch: &str, reference: &str) -> Result<Revision, RelicError> {
+        // {base}{suffix}*
+        // base :
+        //      HEAD, @                 the checked out commit (the tip for other branches)
+        //      pending:N               pending commit number N
+        //      {branch}                tip of a branch
+        //      {tag}                   tagged commit
+        //      {hex}                   history commit whose id starts with this
+        //      {branch}@{upstream}     last pushed commit of a branch, also @{u}
+        //      {branch}@{time}         last commit made at or before a time, eg : main@{yesterday}
+        //      the branch may be left out of the last two
+        // suffix :
+        //      ~N, ~, ^                N commits (or one) before
+        if !self.branch_exists(branch) {
+            return Err(RelicError::BranchNotFound);
+        }
+
+        let (base, suffix) =
+            reference.split_at(reference.find(['~', '^']).unwrap_or(reference.len()));
+        let Revision { branch, commit } = self.revision_base(branch, base)?;
+
+        let mut back: usize = 0;
+        let mut rest = suffix;
+        while let Some(c) = rest.chars().next() {
+            rest = &rest[c.len_utf8()..];
+            let digits = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let count = match (c, &rest[..digits]) {
+                ('~', "") | ('^', "") => 1,
+                ('~', n) => n
+                    .parse::<usize>()
+                    .map_err(|_| RelicError::RevisionInvalid)?,
+                _ => return Err(RelicError::RevisionInvalid),
+            };
+            rest = &rest[digits..];
+            back = back.checked_add(count).ok_or(RelicError::RevisionInvalid)?;
+        }
+
+        let commits = self.branch_commits(&branch);
+        let position = commits
+            .iter()
+            .position(|c| c.timestamp == commit.timestamp)
+            .ok_or(RelicError::CommitNotFound)?;
+        let commit = position
+            .checked_sub(back)
+            .and_then(|p| commits.get(p).cloned())
+            .ok_or(RelicError::CommitNotFound)?;
+
+        Ok(Revision { branch, commit })
+    }
+
+    pub fn revision_pending_index(&self, reference: &str) -> Result<usize, RelicError> {
+        // position of a pending commit of the current branch, plain numbers are positions already
+        if let Ok(index) = reference.parse::<usize>() {
+            return Ok(index);
+        }
+
+        let Revision { branch, commit } = self.revision_parse(reference)?;
+        if branch != self.info.branch {
+            return Err(RelicError::CommitNotFound);
+        }
+        self.pending_get()
+            .iter()
+            .position(|c| c.timestamp == commit.timestamp)
+            .ok_or(RelicError::CommitNotFound)
+    }
+
+    fn revision_base(&self, branch: &str, base: &str) -> Result<Revision, RelicError> {
+        let found = |branch: &str, commit: Option<Commit>| {
+            commit
+                .map(|commit| Revision {
+                    branch: branch.to_string(),
+                    commit,
+                })
+                .ok_or(RelicError::CommitNotFound)
+        };
+
+        if let Some((name, spec)) = base.split_once("@{") {
+            let spec = spec.strip_suffix('}').ok_or(RelicError::RevisionInvalid)?;
+            let name = if name.is_empty() || name == "HEAD" {
+                branch
+            } else {
+                name
+            };
+            if !self.branch_exists(name) {
+                return Err(RelicError::BranchNotFound);
+            }
+
+            return match spec {
+                // history holds what has been pushed
+                "upstream" | "u" => found(name, self.branch_history(name).pop()),
+                spec => {
+                    let time = utils::from_relative_time(spec, utils::get_time())
+                        .ok_or(RelicError::RevisionInvalid)?;
+                    found(
+                        name,
+                        self.branch_commits(name)
+                            .into_iter()
+                            .rev()
+                            .find(|c| c.timestamp <= time),
+                    )
+                }
+            };
+        }
+
+        if base == "HEAD" || base == "@" {
+            let commit = if branch == self.info.branch {
+                self.head_commit()
+            } else {
+                self.branch_head(branch)
+            };
+            return found(branch, commit);
+        }
+
+        if let Some(index) = base.strip_prefix("pending:") {
+            let index = index
+                .parse::<usize>()
+                .map_err(|_| RelicError::RevisionInvalid)?;
+            return found(branch, self.branch_pending(branch).get(index).cloned());
+        }
+
+        if self.branch_exists(base) {
+            return found(base, self.branch_head(base));
+        }
+
+        // tags and ids are looked up on the given branch first
+        let mut branches = self.branch_list();
+        branches.retain(|b| b != branch);
+        branches.insert(0, branch.to_string());
+
+        if let Ok(tag) = self.tag_find(base) {
+            return branches
+                .iter()
+                .find_map(|b| {
+                    self.tag_resolve(&tag, &self.branch_commits(b))
+                        .map(|commit| Revision {
+                            branch: b.clone(),
+                            commit,
+                        })
+                })
+                .ok_or(RelicError::CommitNotFound);
+        }
+
+        if base.is_empty() || !base.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(RelicError::RevisionInvalid);
+        }
+        let base = base.to_lowercase();
+        for b in branches {
+            let mut matches = self
+                .branch_history(&b)
+                .into_iter()
+                .filter(|c| c.id_string().starts_with(&base));
+            match (matches.next(), matches.next()) {
+                (Some(commit), None) => return found(&b, Some(commit)),
+                (Some(_), Some(_)) => return Err(RelicError::RevisionAmbiguous),
+                _ => {}
+            }
+        }
+        Err(RelicError::CommitNotFound)
+    }
+    // #endregion
+}
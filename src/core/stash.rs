@@ -0,0 +1,109 @@
+use std::fs;
+
+use crate::core::{commit::Commit, error::RelicError, paths::RELIC_PATH_STASH, utils, State};
+
+impl State {
+    // #region stash
+    // stashes are pseudo-commits, bound to the branch they were created on
+    fn stash_path(commit: &Commit) -> String {
+        format!("{RELIC_PATH_STASH}/{}.diff", commit.timestamp)
+    }
+
+    pub fn stash_get(&self) -> Vec<Commit> {
+        State::commits_in(RELIC_PATH_STASH)
+    }
+
+    pub fn stash_get_branch(&self, name: &str) -> Vec<Commit> {
+        State::commits_in(&self.branch_path(name, RELIC_PATH_STASH))
+    }
+
+    pub fn stash_find(&self, selector: &str) -> Result<Commit, RelicError> {
+        // by index (as listed) or by name, most recent first
+        let stashes = self.stash_get();
+        if let Ok(index) = selector.parse::<usize>() {
+            if let Some(c) = stashes.get(index) {
+                return Ok(c.clone());
+            }
+        }
+        stashes
+            .into_iter()
+            .rev()
+            .find(|c| c.message == selector)
+            .ok_or(RelicError::StashNotFound)
+    }
+
+    pub fn stash_create(&mut self, name: Option<String>) -> Result<Commit, RelicError> {
+        // stores the tracked changes against upstream, then rolls them back
+        if self.info.detached.is_some() {
+            return Err(RelicError::Detached);
+        }
+
+        let change = self.get_tracked_changes();
+        if change.is_empty() {
+            return Err(RelicError::NoChanges);
+        }
+
+        let commit = Commit {
+            id: None,
+            message: name.unwrap_or(format!("WIP on {}", self.info.branch)),
+            description: "".to_string(),
+            change,
+            timestamp: utils::get_time(),
+            author: "no_one".to_string(),
+        };
+        let _ = fs::create_dir_all(RELIC_PATH_STASH);
+        if fs::write(State::stash_path(&commit), commit.serialise()).is_err() {
+            return Err(RelicError::FileCantOpen);
+        }
+
+        // staged content is part of the stash
+        self.index_clear();
+        let upstream = self.upstream.clone();
+        self.apply_to_working_tree(&commit.change.inverse(), &upstream);
+
+        Ok(commit)
+    }
+
+    pub fn stash_restore(&mut self, commit: &Commit) -> Result<(), RelicError> {
+        // applies a stash on top of the working tree
+        // local changes made since are kept, overlapping edits are reported as conflicts
+        if self.info.detached.is_some() {
+            return Err(RelicError::Detached);
+        }
+
+        let local = self.get_tracked_changes();
+        let change = if local.is_empty() {
+            commit.change.clone()
+        } else {
+            commit
+                .change
+                .transform(&local)
+                .map_err(RelicError::Conflicts)?
+        };
+
+        // upstream may have moved since the stash was created
+        // untracked files in the way are caught here as well
+        change
+            .verify(&self.current)
+            .map_err(RelicError::Conflicts)?;
+
+        let mut target = self.current.clone();
+        target.apply_changes(&change);
+        self.apply_to_working_tree(&change, &target);
+
+        Ok(())
+    }
+
+    pub fn stash_drop(&self, commit: &Commit) {
+        let _ = fs::remove_file(State::stash_path(commit));
+    }
+
+    pub fn stash_clear(&self) -> usize {
+        let stashes = self.stash_get();
+        for c in &stashes {
+            self.stash_drop(c);
+        }
+        stashes.len()
+    }
+    // #endregion
+}
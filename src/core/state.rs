@@ -2,20 +2,26 @@ use serde::{Deserialize, Serialize};
 use std::{
     collections::HashSet,
     fs,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
 };
 
 use crate::core::{
     commit::Commit,
     content_set::{ContentSet, IgnoreSet, TrackingSet},
     error::RelicError,
-    modifications::Change,
+    modifications::{self, Change},
     objects::data::upstream::Upstream,
-    paths::{RELIC_PATH_IGNORE, RELIC_PATH_PENDING, RELIC_PATH_TRACKED, RELIC_PATH_UPSTREAM},
+    paths::{
+        RELIC_PATH_BRANCHES, RELIC_PATH_HISTORY, RELIC_PATH_IGNORE, RELIC_PATH_INDEX,
+        RELIC_PATH_INFO, RELIC_PATH_PARENT, RELIC_PATH_PENDING, RELIC_PATH_ROOT, RELIC_PATH_STASH,
+        RELIC_PATH_TAGS, RELIC_PATH_TRACKED, RELIC_PATH_UPSTREAM,
+    },
     Blob, Content, RelicInfo, Tree,
 };
 
 pub const DEFAULT_BRANCH: &str = "main";
+// file in a pending directory, listing the timestamps of its commits in queue order
+pub const PENDING_ORDER: &str = "order";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct State {
@@ -97,6 +103,21 @@ impl State {
         })
     }
 
+    pub fn initialise_repository(info: &RelicInfo) {
+        // the .relic structure of an empty repository, the ignore file is left to the caller
+        let _ = fs::create_dir(RELIC_PATH_PARENT);
+        let _ = fs::create_dir(RELIC_PATH_HISTORY);
+        let _ = fs::create_dir(RELIC_PATH_PENDING);
+        let _ = fs::create_dir(RELIC_PATH_BRANCHES);
+        let _ = fs::create_dir(RELIC_PATH_STASH);
+        let _ = fs::create_dir(RELIC_PATH_TAGS);
+        let _ = fs::write(RELIC_PATH_INFO, info.serialise());
+        let _ = fs::write(RELIC_PATH_ROOT, "");
+        let _ = fs::write(RELIC_PATH_TRACKED, "");
+        let _ = fs::write(RELIC_PATH_INDEX, "");
+        let _ = fs::write(RELIC_PATH_UPSTREAM, Upstream::empty().serialise());
+    }
+
     pub fn content_at(
         file_name: &String,
         root_path: &PathBuf,
@@ -175,22 +196,198 @@ impl State {
     pub fn get_changes(&self) -> Change {
         Change::get_change_all(&self.upstream, &self.current, Path::new(&self.path))
     }
+
+    pub fn get_tracked_content(&mut self) -> ContentSet {
+        // tracked paths on disk, as well as the ones only left upstream (deleted from disk)
+        let mut result = self.track_set.initialise(&mut self.current);
+        let upstream = self.track_set.initialise(&mut self.upstream.clone());
+        result.files.extend(upstream.files);
+        result.directories.extend(upstream.directories);
+        result
+    }
+
+    pub fn get_tracked_changes(&mut self) -> Change {
+        let tracked_content = self.get_tracked_content();
+        self.get_changes().filter_changes(&tracked_content)
+    }
+
+    pub fn created_paths(change: &Change) -> Vec<PathBuf> {
+        // files a change brings in, to be tracked along with it
+        change
+            .trees
+            .iter()
+            .filter_map(|c_mod| match c_mod {
+                modifications::Tree::CreateBlob(p, n) => Some(PathBuf::from(p).join(n)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn track_add(&mut self, paths: &[PathBuf]) {
+        // same format as relic add, paths without the leading ./
+        let mut tracked = fs::read_to_string(RELIC_PATH_TRACKED)
+            .unwrap_or_default()
+            .split("\n")
+            .filter(|x| !x.is_empty())
+            .map(|x| x.to_string())
+            .collect::<HashSet<String>>();
+        for p in paths {
+            let p = p.strip_prefix(".").unwrap_or(p);
+            tracked.insert(p.to_string_lossy().to_string());
+            self.track_set
+                .files
+                .insert(PathBuf::from(".").join(p).to_string_lossy().to_string());
+        }
+        let _ = fs::write(
+            RELIC_PATH_TRACKED,
+            tracked.into_iter().collect::<Vec<String>>().join("\n"),
+        );
+    }
     // #endregion
 
-    // #region upstream
-    pub fn update_upstream(&mut self, tracked_content: &ContentSet) {
-        // fully fill tracked_content
-        // eg : "lorem/" -> ["lorem/ipsum", "lorem/dolor", "lorem/sit"]
-        // traverse directories and fetch all children
+    // #region working tree
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        // the ignore set matches names, at any depth
+        let names = path
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(n) => Some(n.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+        names.iter().enumerate().any(|(index, name)| {
+            self.ignore_set.directories.contains(name)
+                || (index == names.len() - 1 && self.ignore_set.files.contains(name))
+        })
+    }
+
+    pub fn untracked_collisions(&self, change: &Change, base: &Tree) -> Vec<String> {
+        // files on disk that are not part of base, but would be written over by the change
+        let mut result = vec![];
+        for t in &change.trees {
+            if let modifications::Tree::CreateBlob(p, n) = t {
+                let full = PathBuf::from(p).join(n);
+                if full.exists() && base.get(&full).is_none() {
+                    result.push(full.to_string_lossy().to_string());
+                }
+            }
+        }
+        result
+    }
+
+    pub fn apply_to_working_tree(&mut self, change: &Change, target: &Tree) {
+        // writes a change to disk, target is the tree that the change results in
+        // only paths mentioned in the change are touched, untracked files are left alone
+
+        // deletions first, a blob may be replaced by a tree of the same name
+        // deepest directories first, directories that still hold untracked content are kept
+        let mut deleted_trees = vec![];
+        for t in &change.trees {
+            match t {
+                modifications::Tree::DeleteBlob(p, n) => {
+                    let _ = fs::remove_file(PathBuf::from(p).join(n));
+                }
+                modifications::Tree::DeleteTree(p, n) => {
+                    deleted_trees.push(PathBuf::from(p).join(n));
+                }
+                _ => {}
+            }
+        }
+        deleted_trees.sort_by_key(|p| std::cmp::Reverse(p.components().count()));
+        for p in deleted_trees {
+            let _ = fs::remove_dir(p);
+        }
 
-        let tracked_content = tracked_content.clone().initialise(&mut self.current);
+        let mut written = HashSet::new();
+        for t in &change.trees {
+            match t {
+                modifications::Tree::CreateTree(p, n) => {
+                    let _ = fs::create_dir_all(PathBuf::from(p).join(n));
+                }
+                modifications::Tree::CreateBlob(p, n) => {
+                    written.insert(PathBuf::from(p).join(n));
+                }
+                _ => {}
+            }
+        }
+        for b in &change.blobs {
+            let (p, n) = b.extract_path();
+            written.insert(PathBuf::from(p).join(n));
+        }
 
-        // get changes
-        // filter to only changes in the tracked_content content set
-        let changes = self.get_changes().filter_changes(&tracked_content);
+        for path in written {
+            if let Some(Content::Blob(b)) = target.get(&path) {
+                if let Some(parent) = path.parent() {
+                    let _ = fs::create_dir_all(parent);
+                }
+                let _ = fs::write(&path, &b.content);
+            }
+        }
 
-        // apply changes to current
-        self.upstream.apply_changes(&changes);
+        // keep the in-memory view of the working tree up to date
+        if let Ok(Content::Tree(t)) = State::content_at(
+            &self.path.to_string_lossy().to_string(),
+            &self.path,
+            &self.ignore_set,
+        ) {
+            self.current = t;
+        }
+    }
+
+    pub fn rollback(
+        &mut self,
+        target: &Tree,
+        paths: &[PathBuf],
+        force: bool,
+    ) -> Result<Change, RelicError> {
+        // restores target over the working tree, optionally only at or below the given paths
+        // untracked files that target doesnt know about are left alone
+        // untracked files that target would overwrite are only touched when forced
+        let tracked_content = self.get_tracked_content();
+        let is_tracked = |p: &Path| {
+            let p = p.to_string_lossy().to_string();
+            tracked_content.files.contains(&p) || tracked_content.directories.contains(&p)
+        };
+
+        let mut change = Change::get_change_all(&self.current, target, Path::new(&self.path))
+            .filter_by(|p| !self.is_ignored(p) && (is_tracked(p) || target.get(p).is_some()));
+        if !paths.is_empty() {
+            change = change.filter_paths(paths);
+        }
+
+        let mut untracked = vec![];
+        for t in &change.trees {
+            if let modifications::Tree::DeleteBlob(p, n) = t {
+                let full = PathBuf::from(p).join(n);
+                if !is_tracked(&full) {
+                    untracked.push(full.to_string_lossy().to_string());
+                }
+            }
+        }
+        for b in &change.blobs {
+            let (p, n) = b.extract_path();
+            let full = PathBuf::from(p).join(n);
+            if self.current.get(&full).is_some() && !is_tracked(&full) {
+                untracked.push(full.to_string_lossy().to_string());
+            }
+        }
+        untracked.sort();
+        untracked.dedup();
+        if !force && !untracked.is_empty() {
+            return Err(RelicError::UntrackedOverwritten(untracked));
+        }
+
+        let mut result = self.current.clone();
+        result.apply_changes(&change);
+        self.apply_to_working_tree(&change, &result);
+
+        Ok(change)
+    }
+    // #endregion
+
+    // #region upstream
+    pub fn upstream_apply(&mut self, change: &Change) {
+        self.upstream.apply_changes(change);
         let _ = fs::write(
             RELIC_PATH_UPSTREAM,
             Upstream::from_tree(&self.upstream).serialise(),
@@ -199,17 +396,66 @@ impl State {
     // #endregion
 
     // #region pending
+    fn pending_path(commit: &Commit) -> String {
+        format!("{RELIC_PATH_PENDING}/{}.diff", commit.timestamp)
+    }
+
     pub fn pending_add(&self, commit: Commit) {
         // TODO : use numbering for blob name
         // who knows if two commits are created in the same nanosecond
+        let _ = fs::write(State::pending_path(&commit), commit.serialise());
+    }
+
+    pub fn pending_remove(&self, commit: &Commit) {
+        let _ = fs::remove_file(State::pending_path(commit));
+    }
+
+    pub fn pending_get(&self) -> Vec<Commit> {
+        State::pending_in(RELIC_PATH_PENDING)
+    }
+
+    pub fn pending_in(directory: &str) -> Vec<Commit> {
+        // a commit keeps its timestamp when the queue is reordered, the order is kept on the side
+        // commits missing from it are newer, they come last
+        let order = fs::read_to_string(format!("{directory}/{PENDING_ORDER}"))
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|l| l.parse::<u64>().ok())
+            .collect::<Vec<u64>>();
+        let mut result = State::commits_in(directory);
+        result.sort_by_key(|c| {
+            order
+                .iter()
+                .position(|t| *t == c.timestamp)
+                .unwrap_or(order.len())
+        });
+        result
+    }
+
+    pub fn pending_order_set(&self, commits: &[Commit]) {
         let _ = fs::write(
-            format!("{RELIC_PATH_PENDING}/{}.diff", commit.timestamp),
-            commit.serialise(),
+            format!("{RELIC_PATH_PENDING}/{PENDING_ORDER}"),
+            commits
+                .iter()
+                .map(|c| c.timestamp.to_string())
+                .collect::<Vec<String>>()
+                .join("\n"),
         );
     }
 
-    pub fn pending_get(&self) -> Vec<Commit> {
-        let directories = if let Ok(d) = fs::read_dir(RELIC_PATH_PENDING) {
+    // #endregion
+
+    // #region history
+    pub fn history_get(&self) -> Vec<Commit> {
+        let mut result = State::commits_in(RELIC_PATH_HISTORY);
+        result.sort_by_key(|c| c.id);
+        result
+    }
+    // #endregion
+
+    // #region commits
+    pub fn commits_in(directory: &str) -> Vec<Commit> {
+        let directories = if let Ok(d) = fs::read_dir(directory) {
             d
         } else {
             return vec![];
@@ -219,14 +465,18 @@ impl State {
 
         for d in directories {
             let d = if let Ok(d) = d { d } else { continue };
+            if d.path().extension().is_none_or(|e| e != "diff") {
+                continue;
+            }
             let p = if let Ok(p) = fs::read_to_string(d.path()) {
                 p
             } else {
                 continue;
             };
 
-            if let Some(c) = Commit::deserialise(p) {
-                result.push(c);
+            match Commit::deserialise(p) {
+                Ok(c) => result.push(c),
+                Err(e) => println!("Skipping invalid commit {:?} : {e:?}", d.path()),
             }
         }
 
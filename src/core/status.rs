@@ -0,0 +1,153 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::core::{modifications, modifications::Change, State};
+
+#[derive(Debug, Default)]
+pub struct Changes {
+    // paths relative to the repository root, eg : "./lorem/ipsum.txt"
+    pub modified: Vec<String>,
+    pub added: Vec<String>,
+    pub deleted: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct Status {
+    // upstream -> index
+    pub staged: Changes,
+    // index -> working tree, tracked paths only
+    pub unstaged: Changes,
+    // untracked directories are listed once, eg : "./lorem/"
+    pub untracked: Vec<String>,
+}
+
+impl Changes {
+    fn from_change<F>(change: &Change, is_tracked: F) -> (Changes, Vec<String>, Vec<PathBuf>)
+    where
+        F: Fn(&str) -> bool,
+    {
+        // (tracked changes, untracked blobs, untracked trees)
+        let full_path = |p: &String, n: &String| PathBuf::from(p).join(n);
+
+        let mut result = Changes::default();
+        let mut untracked = vec![];
+        let mut untracked_trees = vec![];
+        for t in &change.trees {
+            let (p, n) = t.extract_data();
+            let path = full_path(&p, &n).to_string_lossy().to_string();
+            match t {
+                modifications::Tree::CreateBlob(_, _) if is_tracked(&path) => {
+                    result.added.push(path)
+                }
+                modifications::Tree::CreateBlob(_, _) => untracked.push(path),
+                modifications::Tree::DeleteBlob(_, _) if is_tracked(&path) => {
+                    result.deleted.push(path)
+                }
+                modifications::Tree::CreateTree(_, _) if !is_tracked(&path) => {
+                    untracked_trees.push(PathBuf::from(&path))
+                }
+                _ => {}
+            }
+        }
+
+        // a blob is modified when its lines change, but it was neither created nor deleted
+        for b in &change.blobs {
+            let (p, n) = b.extract_path();
+            let path = full_path(&p, &n).to_string_lossy().to_string();
+            if is_tracked(&path)
+                && !result.added.contains(&path)
+                && !result.deleted.contains(&path)
+                && !result.modified.contains(&path)
+            {
+                result.modified.push(path);
+            }
+        }
+
+        result.modified.sort();
+        result.added.sort();
+        result.deleted.sort();
+        (result, untracked, untracked_trees)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modified.is_empty() && self.added.is_empty() && self.deleted.is_empty()
+    }
+}
+
+impl State {
+    // #region status
+    pub fn status(&mut self) -> Status {
+        let tracked_content = self.get_tracked_content();
+        let index = self.index_tree();
+
+        let (staged, _, _) = Changes::from_change(
+            &Change::get_change_all(&self.upstream, &index, Path::new(&self.path)),
+            |_| true,
+        );
+
+        // staged content counts as tracked, even if its not in the tracked set
+        let (unstaged, mut untracked, untracked_trees) = Changes::from_change(
+            &Change::get_change_all(&index, &self.current, Path::new(&self.path)),
+            |p| {
+                tracked_content.files.contains(p)
+                    || tracked_content.directories.contains(p)
+                    || index.get(Path::new(p)).is_some()
+            },
+        );
+
+        // collapse untracked directories, only the outermost one is listed
+        let outermost = untracked_trees
+            .iter()
+            .filter(|t| !untracked_trees.iter().any(|o| o != *t && t.starts_with(o)))
+            .cloned()
+            .collect::<Vec<PathBuf>>();
+        untracked.retain(|p| !outermost.iter().any(|t| Path::new(p).starts_with(t)));
+        untracked.extend(
+            outermost
+                .iter()
+                .map(|t| format!("{}/", t.to_string_lossy())),
+        );
+        untracked.sort();
+
+        Status {
+            staged,
+            unstaged,
+            untracked,
+        }
+    }
+
+    pub fn status_ignored(&self) -> Vec<String> {
+        // ignored content is never loaded into the working tree, so it is looked up on disk
+        let mut result = vec![];
+        self.ignored_in(&self.path, &mut result);
+        result.sort();
+        result
+    }
+
+    fn ignored_in(&self, directory: &Path, result: &mut Vec<String>) {
+        let Ok(entries) = fs::read_dir(directory) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_dir = path.is_dir();
+            // the repository itself is always ignored, no point listing it
+            if entry.file_name() == ".relic" && directory == self.path {
+                continue;
+            }
+
+            if self.is_ignored(&path) {
+                result.push(format!(
+                    "{}{}",
+                    path.to_string_lossy(),
+                    if is_dir { "/" } else { "" }
+                ));
+            } else if is_dir {
+                self.ignored_in(&path, result);
+            }
+        }
+    }
+    // #endregion
+}
@@ -0,0 +1,116 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{commit::Commit, error::RelicError, paths::RELIC_PATH_TAGS, utils, State};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Tag {
+    pub name: String,
+    // timestamp of the tagged commit
+    pub commit: u64,
+    // only annotated tags have a tagger, message and timestamp
+    pub annotation: Option<Annotation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Annotation {
+    pub tagger: String,
+    pub message: String,
+    pub timestamp: u64,
+}
+
+impl State {
+    // #region tags
+    // tags are shared by every branch, and point at a commit by its timestamp
+    fn tag_path(name: &str) -> String {
+        format!("{RELIC_PATH_TAGS}/{name}.json")
+    }
+
+    pub fn tag_get(&self) -> Vec<Tag> {
+        let Ok(d) = fs::read_dir(RELIC_PATH_TAGS) else {
+            return vec![];
+        };
+
+        let mut result = d
+            .flatten()
+            .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+            .filter_map(|t| serde_json::from_str::<Tag>(&t).ok())
+            .collect::<Vec<Tag>>();
+        result.sort_by(|a, b| a.name.cmp(&b.name));
+        result
+    }
+
+    pub fn tag_find(&self, name: &str) -> Result<Tag, RelicError> {
+        fs::read_to_string(State::tag_path(name))
+            .ok()
+            .and_then(|t| serde_json::from_str::<Tag>(&t).ok())
+            .ok_or(RelicError::TagNotFound)
+    }
+
+    pub fn tag_create(
+        &self,
+        name: &str,
+        commit: &Commit,
+        message: Option<String>,
+    ) -> Result<Tag, RelicError> {
+        if !State::branch_valid_name(name) {
+            return Err(RelicError::TagNameInvalid);
+        }
+        if self.tag_find(name).is_ok() {
+            return Err(RelicError::TagExists);
+        }
+
+        let tag = Tag {
+            name: name.to_string(),
+            commit: commit.timestamp,
+            annotation: message.map(|message| Annotation {
+                tagger: "no_one".to_string(),
+                message,
+                timestamp: utils::get_time(),
+            }),
+        };
+        let _ = fs::create_dir_all(RELIC_PATH_TAGS);
+        fs::write(State::tag_path(name), serde_json::to_string(&tag).unwrap())
+            .map_err(|_| RelicError::FileCantOpen)?;
+        Ok(tag)
+    }
+
+    pub fn tag_delete(&self, name: &str) -> Result<Tag, RelicError> {
+        let tag = self.tag_find(name)?;
+        fs::remove_file(State::tag_path(name)).map_err(|_| RelicError::FileCantOpen)?;
+        Ok(tag)
+    }
+
+    pub fn tag_import(&self, tag: &Tag) -> bool {
+        // copies a tag from another repository, existing tags are kept as they are
+        if self.tag_find(&tag.name).is_ok() {
+            return false;
+        }
+        let _ = fs::create_dir_all(RELIC_PATH_TAGS);
+        fs::write(
+            State::tag_path(&tag.name),
+            serde_json::to_string(tag).unwrap(),
+        )
+        .is_ok()
+    }
+
+    pub fn tag_retarget(&self, from: u64, to: u64) {
+        // moves the tags of a commit that was folded into another one
+        for mut tag in self.tag_get() {
+            if tag.commit == from {
+                tag.commit = to;
+                let _ = fs::write(
+                    State::tag_path(&tag.name),
+                    serde_json::to_string(&tag).unwrap(),
+                );
+            }
+        }
+    }
+
+    pub fn tag_resolve(&self, tag: &Tag, commits: &[Commit]) -> Option<Commit> {
+        // the tagged commit, if it is part of the given commits
+        commits.iter().find(|c| c.timestamp == tag.commit).cloned()
+    }
+    // #endregion
+}
@@ -1,10 +1,11 @@
 use std::{
     collections::{HashMap, HashSet},
-    path::PathBuf,
+    fs, io,
+    path::{Path, PathBuf},
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 
 use crate::core::{modifications, Blob, Content, Tree};
 
@@ -187,6 +188,22 @@ fn generate_subtree(c: &Content) -> String {
     result.join("\n")
 }
 
+pub fn copy_recursive(from: &Path, to: &Path) -> io::Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else {
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
 pub fn get_time() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -200,3 +217,78 @@ pub fn into_human_readable(t: u64) -> String {
         .format("%Y-%m-%d %H:%M:%S")
         .to_string()
 }
+
+pub fn from_human_readable(s: &str) -> Option<u64> {
+    // inverse of into_human_readable, the time of day is optional
+    // eg : "2025-5-26", "2025-5-26 16:30", "2025-5-26 16:30:00"
+    let time = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(s, f).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })?;
+    u64::try_from(time.and_utc().timestamp_millis()).ok()
+}
+
+pub fn from_relative_time(s: &str, now: u64) -> Option<u64> {
+    // "now", "yesterday", "3 days ago", "2.weeks.ago", or any time from_human_readable accepts
+    // months and years are approximated as 30 and 365 days
+    if let Some(t) = from_human_readable(s) {
+        return Some(t);
+    }
+
+    let words = s
+        .split(|c: char| c.is_whitespace() || c == '.' || c == '_')
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<&str>>();
+    let (amount, unit) = match words.as_slice() {
+        ["now"] => return Some(now),
+        ["yesterday"] => (1, "day"),
+        [amount, unit, "ago"] => (amount.parse::<u64>().ok()?, *unit),
+        _ => return None,
+    };
+
+    let seconds = match unit.strip_suffix('s').unwrap_or(unit) {
+        "second" | "sec" => 1,
+        "minute" | "min" => 60,
+        "hour" => 60 * 60,
+        "day" => 24 * 60 * 60,
+        "week" => 7 * 24 * 60 * 60,
+        "month" => 30 * 24 * 60 * 60,
+        "year" => 365 * 24 * 60 * 60,
+        _ => return None,
+    };
+    now.checked_sub(amount.checked_mul(seconds * 1000)?)
+}
+
+pub fn from_debug_string(s: &str) -> Option<String> {
+    // reads a string written with {:?}, the way change text was stored before json escaping
+    let mut chars = s.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut result = String::new();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        result.push(match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            '\\' => '\\',
+            '"' => '"',
+            '\'' => '\'',
+            'u' => {
+                if chars.next()? != '{' {
+                    return None;
+                }
+                let hex = chars.by_ref().take_while(|c| *c != '}').collect::<String>();
+                char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?
+            }
+            _ => return None,
+        });
+    }
+    Some(result)
+}
@@ -0,0 +1,208 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use proptest::prelude::*;
+
+use relic_vcs::core::{
+    modifications::{self, Change},
+    Blob, Content, Tree,
+};
+
+#[derive(Debug, Clone)]
+enum Node {
+    File(String),
+    Directory(BTreeMap<String, Node>),
+}
+
+fn name() -> impl Strategy<Value = String> {
+    // small pool so that generated trees overlap, includes characters that need encoding
+    prop::sample::select(vec!["a", "b", "c", "d e", "f%2F", "g.rs"]).prop_map(String::from)
+}
+
+fn line() -> impl Strategy<Value = String> {
+    prop_oneof![
+        4 => "[ab]{0,2}",
+        1 => "[ \"\\\\\t\r]{0,3}",
+        1 => any::<String>().prop_map(|s| s.replace('\n', "")),
+    ]
+}
+
+fn content() -> impl Strategy<Value = String> {
+    prop::collection::vec(line(), 0..8).prop_map(|l| l.join("\n"))
+}
+
+fn node() -> impl Strategy<Value = Node> {
+    content()
+        .prop_map(Node::File)
+        .prop_recursive(3, 24, 4, |inner| {
+            prop::collection::btree_map(name(), inner, 0..4).prop_map(Node::Directory)
+        })
+}
+
+fn tree() -> impl Strategy<Value = Tree> {
+    prop::collection::btree_map(name(), node(), 0..5).prop_map(|m| {
+        let mut t = Tree::new();
+        t.content = build(Path::new("."), &m);
+        t
+    })
+}
+
+fn build(path: &Path, map: &BTreeMap<String, Node>) -> Vec<Content> {
+    map.iter()
+        .map(|(n, node)| match node {
+            Node::File(c) => Content::Blob(Blob {
+                name: n.clone(),
+                content: c.clone(),
+            }),
+            Node::Directory(m) => Content::Tree(Tree {
+                path: path.join(n),
+                name: n.clone(),
+                content: build(&path.join(n), m),
+            }),
+        })
+        .collect()
+}
+
+fn flatten(tree: &Tree) -> BTreeMap<String, Option<String>> {
+    // path -> content (None for directories)
+    fn inner(path: PathBuf, tree: &Tree, result: &mut BTreeMap<String, Option<String>>) {
+        for c in &tree.content {
+            match c {
+                Content::Tree(t) => {
+                    result.insert(path.join(&t.name).to_string_lossy().to_string(), None);
+                    inner(path.join(&t.name), t, result);
+                }
+                Content::Blob(b) => {
+                    result.insert(
+                        path.join(&b.name).to_string_lossy().to_string(),
+                        Some(b.content.clone()),
+                    );
+                }
+            }
+        }
+    }
+
+    let mut result = BTreeMap::new();
+    inner(PathBuf::from("."), tree, &mut result);
+    result
+}
+
+fn change(from: &Tree, to: &Tree) -> Change {
+    Change::get_change_all(from, to, Path::new("."))
+}
+
+fn applied(tree: &Tree, change: &Change) -> Tree {
+    let mut tree = tree.clone();
+    tree.apply_changes(change);
+    tree
+}
+
+proptest! {
+    #[test]
+    fn apply_reaches_target(a in tree(), b in tree()) {
+        let c = change(&a, &b);
+        prop_assert_eq!(flatten(&applied(&a, &c)), flatten(&b));
+    }
+
+    #[test]
+    fn unapply_restores_source(a in tree(), b in tree()) {
+        let c = change(&a, &b);
+
+        let mut t = applied(&a, &c);
+        t.unapply_changes(&c);
+        prop_assert_eq!(flatten(&t), flatten(&a));
+
+        let mut t = b.clone();
+        t.unapply_changes(&c);
+        prop_assert_eq!(flatten(&t), flatten(&a));
+    }
+
+    #[test]
+    fn serialisation_round_trips(a in tree(), b in tree()) {
+        let mut c = change(&a, &b);
+        // serialisation groups line changes by file
+        c.blobs.sort_by_key(|m| m.extract_path());
+
+        let s = c.serialise_changes();
+        prop_assert_eq!(Change::deserialise_changes(s), Ok(c));
+    }
+
+    #[test]
+    fn compose_matches_sequential_application(a in tree(), b in tree(), c in tree()) {
+        let composed = Change::compose(&change(&a, &b), &change(&b, &c));
+        prop_assert_eq!(flatten(&applied(&a, &composed)), flatten(&c));
+    }
+
+    #[test]
+    fn transform_commutes(a in tree(), b in tree(), c in tree()) {
+        let (left, right) = (change(&a, &b), change(&a, &c));
+        if let (Ok(l), Ok(r)) = (left.transform(&right), right.transform(&left)) {
+            prop_assert_eq!(
+                flatten(&applied(&applied(&a, &right), &l)),
+                flatten(&applied(&applied(&a, &left), &r))
+            );
+        }
+    }
+
+    #[test]
+    fn change_verifies_against_source(a in tree(), b in tree()) {
+        prop_assert_eq!(change(&a, &b).verify(&a), Ok(()));
+    }
+
+    #[test]
+    fn transform_verifies_against_concurrent_result(a in tree(), b in tree(), c in tree()) {
+        let (left, right) = (change(&a, &b), change(&a, &c));
+        if let Ok(l) = left.transform(&right) {
+            prop_assert_eq!(l.verify(&applied(&a, &right)), Ok(()));
+        }
+    }
+}
+
+#[test]
+fn renames_are_detected_by_shared_content() {
+    let file = |name: &str, content: &str| {
+        Content::Blob(Blob {
+            name: name.to_string(),
+            content: content.to_string(),
+        })
+    };
+    let mut a = Tree::new();
+    a.content = vec![
+        file("old.rs", "one\ntwo\nthree\nfour\n"),
+        file("gone.rs", "unrelated\n"),
+    ];
+    let mut b = Tree::new();
+    b.content = vec![
+        file("new.rs", "one\ntwo\nthree\nfive\n"),
+        file("other.rs", "something else\n"),
+    ];
+
+    assert_eq!(
+        change(&a, &b).get_renames(),
+        vec![(PathBuf::from("./old.rs"), PathBuf::from("./new.rs"))]
+    );
+}
+
+#[test]
+fn changes_with_debug_escapes_still_parse() {
+    // text used to be written with {:?}, before json escaping
+    let text = "\u{1b}[0m\0\u{301}\t\"quoted\"";
+    let c = Change {
+        trees: vec![modifications::Tree::CreateBlob(
+            ".".to_string(),
+            "f".to_string(),
+        )],
+        blobs: vec![modifications::Blob::Create(
+            ".".to_string(),
+            "f".to_string(),
+            0,
+            text.to_string(),
+        )],
+    };
+    let json = serde_json::to_string(text).unwrap();
+    let old = c.serialise_changes().replace(&json, &format!("{text:?}"));
+    assert_ne!(old, c.serialise_changes());
+    assert_eq!(Change::deserialise_changes(old), Ok(c));
+}
@@ -0,0 +1,111 @@
+use proptest::prelude::*;
+
+use relic_vcs::core::{
+    commit::Commit,
+    error::{ParseError, RelicError},
+    modifications::{self, Change},
+};
+
+fn commit() -> impl Strategy<Value = Commit> {
+    (
+        prop::option::of(0..=u32::MAX),
+        any::<String>(),
+        any::<String>(),
+        any::<u64>(),
+        "[a-z_]{1,8}",
+        prop::collection::vec((0..10usize, any::<String>()), 0..5),
+    )
+        .prop_map(
+            |(id, message, description, timestamp, author, lines)| Commit {
+                id,
+                message,
+                description,
+                change: Change {
+                    trees: vec![modifications::Tree::CreateBlob(
+                        ".".to_string(),
+                        "file name".to_string(),
+                    )],
+                    blobs: lines
+                        .into_iter()
+                        .map(|(l, t)| {
+                            modifications::Blob::Create(
+                                ".".to_string(),
+                                "file name".to_string(),
+                                l,
+                                t,
+                            )
+                        })
+                        .collect(),
+                },
+                timestamp,
+                author,
+            },
+        )
+}
+
+proptest! {
+    #[test]
+    fn serialisation_round_trips(c in commit()) {
+        let parsed = Commit::deserialise(c.serialise()).unwrap();
+        prop_assert_eq!(parsed.serialise(), c.serialise());
+        prop_assert_eq!(parsed.id, c.id);
+    }
+
+    #[test]
+    fn malformed_commits_do_not_panic(s in any::<String>()) {
+        let _ = Commit::deserialise(s.clone());
+        let _ = Change::deserialise_changes(s);
+    }
+
+    #[test]
+    fn truncated_commits_do_not_panic(c in commit(), cut in any::<prop::sample::Index>()) {
+        let s = c.serialise();
+        let cut = cut.index(s.len() + 1);
+        if s.is_char_boundary(cut) {
+            let _ = Commit::deserialise(s[..cut].to_string());
+        }
+    }
+}
+
+#[test]
+fn malformed_commits_report_errors() {
+    let cases = [
+        ("", RelicError::Parse(0, ParseError::MissingChange)),
+        (
+            "= LOCAL 0 \"\" \"\"\n=",
+            RelicError::Parse(0, ParseError::InvalidMetadata),
+        ),
+        (
+            "= LOCAL x \"\" \"\" no_one\n=",
+            RelicError::Parse(0, ParseError::InvalidTimestamp),
+        ),
+        (
+            "= LOCAL 0 \" \"\" no_one\n=",
+            RelicError::Parse(0, ParseError::InvalidEncoding),
+        ),
+        (
+            "= LOCAL 0 \"%FF\" \"\" no_one\n=",
+            RelicError::Parse(0, ParseError::InvalidEncoding),
+        ),
+        (
+            "= LOCAL 0 \"\" \"\" no_one\n+ F .\n=",
+            RelicError::Parse(1, ParseError::InvalidTree),
+        ),
+        (
+            "= LOCAL 0 \"\" \"\" no_one\n=\n+ 0 \"\"",
+            RelicError::Parse(2, ParseError::MissingBlobHeader),
+        ),
+        (
+            "= LOCAL 0 \"\" \"\" no_one\n=\n| . a\n+ x \"\"",
+            RelicError::Parse(3, ParseError::InvalidLineIndex),
+        ),
+        (
+            "= LOCAL 0 \"\" \"\" no_one\n=\n| . a\n+ 0 \"unterminated",
+            RelicError::Parse(3, ParseError::InvalidEncoding),
+        ),
+    ];
+
+    for (s, e) in cases {
+        assert_eq!(Commit::deserialise(s.to_string()).unwrap_err(), e, "{s:?}");
+    }
+}
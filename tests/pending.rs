@@ -0,0 +1,88 @@
+use std::{env, fs, path::PathBuf, thread, time::Duration};
+
+use relic_vcs::core::{commit::Commit, RelicInfo, State};
+
+fn commit(file: &str, text: &str) -> Commit {
+    // commits are identified by their timestamp, in milliseconds
+    thread::sleep(Duration::from_millis(2));
+    fs::write(file, text).unwrap();
+    let mut state = State::create(PathBuf::from(".")).unwrap();
+    state.track_add(&[PathBuf::from(".").join(file)]);
+    state
+        .commit_create(
+            file.to_string(),
+            "".to_string(),
+            Some(&[PathBuf::from(".").join(file)]),
+            false,
+        )
+        .unwrap()
+}
+
+// the repository is worked on from its root, so everything happens in a single test
+#[test]
+fn rewrites_keep_references() {
+    let root = env::temp_dir().join(format!("relic-pending-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    env::set_current_dir(&root).unwrap();
+    State::initialise_repository(&RelicInfo::default());
+
+    let a = commit("a.txt", "a\n");
+    let b = commit("b.txt", "b\n");
+    let c = commit("c.txt", "c\n");
+    let mut state = State::create(PathBuf::from(".")).unwrap();
+    state.tag_create("first", &a, None).unwrap();
+    state.tag_create("last", &c, None).unwrap();
+    let tagged = |state: &State, name: &str| {
+        state
+            .tag_resolve(&state.tag_find(name).unwrap(), &state.pending_get())
+            .map(|c| c.message)
+    };
+
+    // moving keeps every commit's timestamp, tags follow their commit
+    state.pending_move(2, 0).unwrap();
+    let messages = |state: &State| {
+        state
+            .pending_get()
+            .into_iter()
+            .map(|c| c.message)
+            .collect::<Vec<String>>()
+    };
+    assert_eq!(messages(&state), ["c.txt", "a.txt", "b.txt"]);
+    assert_eq!(tagged(&state, "first").as_deref(), Some("a.txt"));
+    assert_eq!(tagged(&state, "last").as_deref(), Some("c.txt"));
+    assert_eq!(state.pending_get()[0].timestamp, c.timestamp);
+
+    // squashing moves the tags of the folded commit onto the result
+    state.pending_squash(1).unwrap();
+    assert_eq!(messages(&state), ["c.txt", "b.txt"]);
+    assert_eq!(tagged(&state, "first").as_deref(), Some("c.txt"));
+    assert_eq!(tagged(&state, "last").as_deref(), Some("c.txt"));
+
+    // splitting leaves the tags on the first half, later commits are untouched
+    state.tag_create("second", &b, None).unwrap();
+    state
+        .pending_split(0, &[PathBuf::from("a.txt")], "a only".to_string(), None)
+        .unwrap();
+    assert_eq!(messages(&state), ["a only", "c.txt", "b.txt"]);
+    assert_eq!(tagged(&state, "last").as_deref(), Some("a only"));
+    assert_eq!(tagged(&state, "second").as_deref(), Some("b.txt"));
+    assert_eq!(state.pending_get()[2].timestamp, b.timestamp);
+
+    // squashing the most recent commits keeps the identity of the oldest, every tag follows
+    let d = commit("d.txt", "d\n");
+    let mut state = State::create(PathBuf::from(".")).unwrap();
+    state.tag_create("fourth", &d, None).unwrap();
+    let oldest = state.pending_get()[1].timestamp;
+    state
+        .pending_squash_recent(3, Some("all".to_string()), None)
+        .unwrap();
+    assert_eq!(messages(&state), ["a only", "all"]);
+    assert_eq!(state.pending_get()[1].timestamp, oldest);
+    assert_eq!(tagged(&state, "second").as_deref(), Some("all"));
+    assert_eq!(tagged(&state, "fourth").as_deref(), Some("all"));
+    assert_eq!(tagged(&state, "last").as_deref(), Some("a only"));
+
+    env::set_current_dir(env::temp_dir()).unwrap();
+    let _ = fs::remove_dir_all(&root);
+}
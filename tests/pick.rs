@@ -0,0 +1,44 @@
+use std::{env, fs, path::PathBuf};
+
+use relic_vcs::core::{paths::RELIC_PATH_TRACKED, RelicInfo, State};
+
+// the repository is worked on from its root, so everything happens in a single test
+#[test]
+fn picked_files_are_tracked() {
+    let root = env::temp_dir().join(format!("relic-pick-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    env::set_current_dir(&root).unwrap();
+    State::initialise_repository(&RelicInfo::default());
+
+    // a file committed on another branch
+    let mut state = State::create(PathBuf::from(".")).unwrap();
+    state.branch_create("other").unwrap();
+    state.branch_switch("other").unwrap();
+    fs::write("f.txt", "one\n").unwrap();
+    let mut state = State::create(PathBuf::from(".")).unwrap();
+    state.track_add(&[PathBuf::from("./f.txt")]);
+    let commit = state
+        .commit_create(
+            "f".to_string(),
+            "".to_string(),
+            Some(&[PathBuf::from("./f.txt")]),
+            false,
+        )
+        .unwrap();
+    state.branch_switch("main").unwrap();
+    assert!(!PathBuf::from("f.txt").exists());
+    // untracked on main, as relic remove would
+    fs::write(RELIC_PATH_TRACKED, "").unwrap();
+
+    // picked onto main, later edits to it show up as changes
+    let mut state = State::create(PathBuf::from(".")).unwrap();
+    state.pick("other", &commit).unwrap();
+    assert_eq!(fs::read_to_string("f.txt").unwrap(), "one\n");
+    fs::write("f.txt", "one\ntwo\n").unwrap();
+    let mut state = State::create(PathBuf::from(".")).unwrap();
+    assert!(!state.get_tracked_changes().is_empty());
+
+    env::set_current_dir(env::temp_dir()).unwrap();
+    let _ = fs::remove_dir_all(&root);
+}
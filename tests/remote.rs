@@ -0,0 +1,101 @@
+use std::{env, fs, path::PathBuf};
+
+use relic_vcs::core::{error::RelicError, RelicInfo, State};
+
+// repositories are worked on from their root, so everything happens in a single test
+#[test]
+fn push_clone_pull() {
+    let root = env::temp_dir().join(format!("relic-remote-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    for d in ["server", "local"] {
+        fs::create_dir_all(root.join(d)).unwrap();
+        env::set_current_dir(root.join(d)).unwrap();
+        State::initialise_repository(&RelicInfo::default());
+    }
+
+    // local : commit a file and push it to the server
+    env::set_current_dir(root.join("local")).unwrap();
+    fs::write("f.txt", "one\n").unwrap();
+    let mut local = State::create(PathBuf::from(".")).unwrap();
+    local.track_add(&[PathBuf::from("./f.txt")]);
+    local
+        .commit_create(
+            "one".to_string(),
+            "".to_string(),
+            Some(&[PathBuf::from("./f.txt")]),
+            false,
+        )
+        .unwrap();
+    assert_eq!(local.push().err(), Some(RelicError::RemoteNotSet));
+    local.remote_set("../server").unwrap();
+    let pushed = local.push().unwrap();
+    assert_eq!(pushed.len(), 1);
+    assert_eq!(pushed[0].id, Some(0));
+    assert!(local.pending_get().is_empty());
+    assert_eq!(local.history_get().len(), 1);
+
+    // the server has it checked out
+    assert_eq!(
+        fs::read_to_string(root.join("server/f.txt")).unwrap(),
+        "one\n"
+    );
+
+    // clone : gets the history and working tree, then pushes a change of its own
+    let server = format!("file://{}", root.join("server").display());
+    let clone = State::clone_into(&server, &root.join("clone")).unwrap();
+    assert_eq!(fs::read_to_string("f.txt").unwrap(), "one\n");
+    assert_eq!(clone.history_get().len(), 1);
+    fs::write("f.txt", "one\ntwo\n").unwrap();
+    let mut clone = State::create(PathBuf::from(".")).unwrap();
+    clone
+        .commit_create(
+            "two".to_string(),
+            "".to_string(),
+            Some(&[PathBuf::from("./f.txt")]),
+            false,
+        )
+        .unwrap();
+    assert_eq!(clone.push().unwrap()[0].id, Some(1));
+
+    // local : pushing is refused until the new commit is pulled
+    env::set_current_dir(root.join("local")).unwrap();
+    fs::write("g.txt", "local\n").unwrap();
+    let mut local = State::create(PathBuf::from(".")).unwrap();
+    local.track_add(&[PathBuf::from("./g.txt")]);
+    local
+        .commit_create(
+            "three".to_string(),
+            "".to_string(),
+            Some(&[PathBuf::from("./g.txt")]),
+            false,
+        )
+        .unwrap();
+    assert_eq!(local.push().err(), Some(RelicError::RemoteAhead));
+    assert_eq!(local.pull().unwrap().len(), 1);
+    assert_eq!(fs::read_to_string("f.txt").unwrap(), "one\ntwo\n");
+    assert_eq!(local.pending_get().len(), 1);
+
+    // fetching again replaces the previous copy of the remote as a whole
+    let branch = local.info.branch.clone();
+    assert_eq!(local.fetch().unwrap(), [(branch.clone(), 0)]);
+    assert_eq!(local.fetched_history(&branch).len(), 2);
+    assert!(!PathBuf::from(".relic/remote.tmp").exists());
+    assert_eq!(local.push().unwrap()[0].id, Some(2));
+
+    // a new remote gets the whole history, even with nothing pending
+    fs::create_dir_all(root.join("mirror")).unwrap();
+    env::set_current_dir(root.join("mirror")).unwrap();
+    State::initialise_repository(&RelicInfo::default());
+    env::set_current_dir(root.join("local")).unwrap();
+    let mut local = State::create(PathBuf::from(".")).unwrap();
+    local.remote_set("../mirror").unwrap();
+    assert_eq!(local.push().unwrap().len(), 3);
+    assert!(local.push().unwrap().is_empty());
+    assert_eq!(
+        fs::read_to_string(root.join("mirror/g.txt")).unwrap(),
+        "local\n"
+    );
+
+    env::set_current_dir(env::temp_dir()).unwrap();
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,53 @@
+use proptest::prelude::*;
+
+use relic_vcs::core::utils;
+
+proptest! {
+    #[test]
+    fn human_readable_time_round_trips(seconds in 0..4_102_444_800u64) {
+        // human readable times are only precise to the second
+        let t = seconds * 1000;
+        prop_assert_eq!(utils::from_human_readable(&utils::into_human_readable(t)), Some(t));
+    }
+
+    #[test]
+    fn debug_strings_round_trip(s in any::<String>()) {
+        prop_assert_eq!(utils::from_debug_string(&format!("{s:?}")), Some(s));
+    }
+}
+
+#[test]
+fn human_readable_time_is_optional() {
+    assert_eq!(
+        utils::from_human_readable("2025-5-26"),
+        utils::from_human_readable("2025-05-26 00:00:00")
+    );
+    assert_eq!(
+        utils::from_human_readable("2025-5-26 16:30"),
+        utils::from_human_readable("2025-05-26 16:30:00")
+    );
+    assert_eq!(utils::from_human_readable("yesterday"), None);
+}
+
+#[test]
+fn relative_time_counts_back_from_now() {
+    let now = utils::from_human_readable("2025-5-26 16:30").unwrap();
+    assert_eq!(utils::from_relative_time("now", now), Some(now));
+    assert_eq!(
+        utils::from_relative_time("yesterday", now),
+        utils::from_human_readable("2025-5-25 16:30")
+    );
+    assert_eq!(
+        utils::from_relative_time("3 hours ago", now),
+        utils::from_human_readable("2025-5-26 13:30")
+    );
+    assert_eq!(
+        utils::from_relative_time("2.weeks.ago", now),
+        utils::from_human_readable("2025-5-12 16:30")
+    );
+    assert_eq!(
+        utils::from_relative_time("2025-1-1", now),
+        utils::from_human_readable("2025-1-1")
+    );
+    assert_eq!(utils::from_relative_time("3 fortnights ago", now), None);
+}